@@ -0,0 +1,167 @@
+//! Discovering modules from the file system for `#[layers(auto = "...")]`.
+//!
+//! Each `.rs` file (or directory with a `mod.rs`) in the directory is a module.
+//! The module declares its attributes in comments at the top of the file:
+//!
+//! ```ignore
+//! // #![public]
+//! // #![depends_on(utils)]
+//! // #![visible_to(api)]
+//!
+//! //! Docs for the module
+//! ```
+//!
+//! `#![public]` makes the module `pub`, everything else is the same as
+//! the attribute on the `extern crate` placeholder. Since there is no central list,
+//! the modules don't need to be in any order.
+//!
+//! The directory must be where rustc looks for the files of the `#[layers]` module,
+//! since the modules are declared as `pub mod x;`. Every discovered file is also included with
+//! `include_str!`, so changes to it re-run the macro. Cargo doesn't know about the directory
+//! itself, so adding a file requires touching the file with the `#[layers]` module.
+//!
+//! A proc macro can't point to a span in another file, so errors in the comments
+//! are spanned at the `auto = "..."` argument, with the file and line in the message.
+
+use std::path::Path;
+
+use proc_macro2::{Span as Span2, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+
+/// List the modules in `dir` as `extern crate` placeholders, sorted by name,
+/// each followed by an `include_str!` of its file
+pub(crate) fn discover_layers(dir: &syn::LitStr) -> syn::Result<Vec<syn::Item>> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let rel_dir = dir.value();
+    let abs_dir = Path::new(&manifest_dir).join(&rel_dir);
+    let entries = std::fs::read_dir(&abs_dir).map_err(|e| {
+        syn::Error::new_spanned(
+            dir,
+            format!("[LC0020] cannot read directory `{rel_dir}`: {e}"),
+        )
+    })?;
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| {
+            syn::Error::new_spanned(
+                dir,
+                format!("[LC0020] cannot read directory `{rel_dir}`: {e}"),
+            )
+        })?;
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if path.is_dir() {
+            let mod_rs = path.join("mod.rs");
+            if mod_rs.is_file() {
+                let display_path = format!("{rel_dir}/{file_name}/mod.rs");
+                files.push((file_name, mod_rs, display_path));
+            }
+        } else if let Some(name) = file_name.strip_suffix(".rs") {
+            let display_path = format!("{rel_dir}/{file_name}");
+            files.push((name.to_string(), path, display_path));
+        }
+    }
+    files.sort();
+
+    let mut items = Vec::with_capacity(files.len() * 2);
+    for (name, path, display_path) in files {
+        items.push(discover_layer(dir, &name, &path, &display_path)?);
+        // track the file, so the attributes are read again when it changes
+        let path = syn::LitStr::new(&path.to_string_lossy(), dir.span());
+        items.push(syn::parse_quote! {
+            const _: &str = include_str!(#path);
+        });
+    }
+    Ok(items)
+}
+
+/// Make the `extern crate` placeholder from the comments at the top of the file
+fn discover_layer(
+    dir: &syn::LitStr,
+    name: &str,
+    path: &Path,
+    display_path: &str,
+) -> syn::Result<syn::Item> {
+    let span = dir.span();
+    let ident = syn::parse_str::<syn::Ident>(name).map_err(|_| {
+        syn::Error::new_spanned(
+            dir,
+            format!("[LC0020] `{display_path}` is not a valid module name"),
+        )
+    })?;
+    let ident = syn::Ident::new(&ident.to_string(), span);
+    let source = std::fs::read_to_string(path).map_err(|e| {
+        syn::Error::new_spanned(dir, format!("[LC0020] cannot read `{display_path}`: {e}"))
+    })?;
+
+    let mut is_pub = false;
+    let mut attrs = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//!") || line.starts_with("///") {
+            continue;
+        }
+        let Some(comment) = line.strip_prefix("//") else {
+            // end of the leading comments
+            break;
+        };
+        let comment = comment.trim();
+        if !comment.starts_with("#!") {
+            continue;
+        }
+        let marker_error = |e: &dyn std::fmt::Display| {
+            syn::Error::new_spanned(
+                dir,
+                format!(
+                    "[LC0020] {display_path}:{}: invalid module attribute: {e}",
+                    i + 1
+                ),
+            )
+        };
+        let tokens = comment
+            .parse::<TokenStream2>()
+            .map_err(|e| marker_error(&e))?;
+        let parsed = syn::parse::Parser::parse2(syn::Attribute::parse_inner, respan(tokens, span))
+            .map_err(|e| marker_error(&e))?;
+        for mut attr in parsed {
+            if attr.path().is_ident("public") {
+                attr.meta
+                    .require_path_only()
+                    .map_err(|e| marker_error(&e))?;
+                is_pub = true;
+                continue;
+            }
+            attr.style = syn::AttrStyle::Outer;
+            attrs.push(attr);
+        }
+    }
+
+    let vis = if is_pub {
+        quote! { pub }
+    } else {
+        quote! {}
+    };
+    Ok(syn::parse_quote! {
+        #(#attrs)*
+        #vis extern crate #ident;
+    })
+}
+
+/// Point all the tokens to `span`, so errors show up on the `auto = "..."` argument
+fn respan(tokens: TokenStream2, span: Span2) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|mut tt| {
+            if let TokenTree::Group(group) = &tt {
+                let mut new_group =
+                    proc_macro2::Group::new(group.delimiter(), respan(group.stream(), span));
+                new_group.set_span(span);
+                tt = TokenTree::Group(new_group);
+            } else {
+                tt.set_span(span);
+            }
+            tt
+        })
+        .collect()
+}
@@ -0,0 +1,123 @@
+//! Parser for the function-like [`define_layers!`](crate::define_layers) syntax.
+//!
+//! The DSL is lowered into the same `mod src { extern crate x; }` form that
+//! `#[layers]` takes, so both forms share the validation and the code generation.
+
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+use crate::{DependsOnArgs, LayersArgs};
+
+/// `#[layers(...)]? mod src { <layer>* }`
+pub(crate) struct LayersDsl {
+    pub args: LayersArgs,
+    pub module: syn::ItemMod,
+}
+
+impl syn::parse::Parse for LayersDsl {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut args = LayersArgs::default();
+        let mut attrs = Vec::new();
+        for attr in input.call(syn::Attribute::parse_outer)? {
+            if attr.path().is_ident("layers") {
+                if let syn::Meta::List(_) = attr.meta {
+                    args = attr.parse_args()?;
+                }
+            } else {
+                attrs.push(attr);
+            }
+        }
+        let vis = input.parse::<syn::Visibility>()?;
+        let mod_token = input.parse::<syn::Token![mod]>()?;
+        let ident = input.parse::<syn::Ident>()?;
+        let content;
+        let brace = syn::braced!(content in input);
+
+        let mut items = Vec::new();
+        while !content.is_empty() {
+            items.push(content.parse::<LayerDsl>()?.into_item());
+        }
+
+        let module = syn::ItemMod {
+            attrs,
+            vis,
+            unsafety: None,
+            mod_token,
+            ident,
+            content: Some((brace, items)),
+            semi: None,
+        };
+        Ok(Self { args, module })
+    }
+}
+
+/// `#[attrs]* pub? name (-> [deps, ...])? ;`
+struct LayerDsl {
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    ident: syn::Ident,
+    deps: Vec<DependsOnArgs>,
+    semi: syn::Token![;],
+}
+
+impl syn::parse::Parse for LayerDsl {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let ident = input.parse()?;
+        let mut deps = Vec::new();
+        if input.peek(syn::Token![->]) {
+            input.parse::<syn::Token![->]>()?;
+            let content;
+            syn::bracketed!(content in input);
+            let list =
+                syn::punctuated::Punctuated::<DependsOnArgs, syn::Token![,]>::parse_terminated(
+                    &content,
+                )?;
+            deps.extend(list);
+        }
+        let semi = input.parse()?;
+        Ok(Self {
+            attrs,
+            vis,
+            ident,
+            deps,
+            semi,
+        })
+    }
+}
+
+impl LayerDsl {
+    /// Lower to `#[depends_on(dep)]* vis extern crate ident;`
+    ///
+    /// The generated attributes are spanned to the dependency in the DSL,
+    /// so errors about an edge point to it.
+    fn into_item(self) -> syn::Item {
+        let Self {
+            attrs,
+            vis,
+            ident,
+            deps,
+            semi,
+        } = self;
+        let dep_attrs = deps.iter().map(|dep| {
+            let span = dep.ident.span();
+            let dep_ident = &dep.ident;
+            let path = match &dep.items {
+                None => quote! { #dep_ident },
+                Some(items) => quote_spanned! { span=> #dep_ident::{#(#items),*} },
+            };
+            let deprecated = dep
+                .deprecated
+                .as_ref()
+                .map(|note| quote_spanned! { span=> , deprecated = #note });
+            quote_spanned! { span=> #[depends_on(#path #deprecated)] }
+        });
+        let extern_token = quote_spanned! { ident.span()=> extern crate };
+        syn::parse_quote_spanned! { semi.span()=>
+            #(#attrs)*
+            #(#dep_attrs)*
+            #vis #extern_token #ident #semi
+        }
+    }
+}
@@ -0,0 +1,340 @@
+//! JSON description of a `#[layers]` module for external tools.
+//!
+//! Written to `$LAYERED_CRATE_GRAPH_DIR/<crate>.<module>.json` when the environment
+//! variable is set, where `<module>` is the path from `#[layers(at = ...)]` and the module name,
+//! separated by dots. See the "Graph JSON" section in the README for the schema.
+
+use std::fmt::Write as _;
+
+use proc_macro2::{Span as Span2, TokenStream as TokenStream2, TokenTree};
+
+use crate::{DepEdge, DepsGraph, ModuleDecl};
+
+/// Version of the JSON schema, bumped on breaking changes
+const SCHEMA_VERSION: usize = 1;
+
+/// Environment variable for the output directory
+const GRAPH_DIR_ENV: &str = "LAYERED_CRATE_GRAPH_DIR";
+
+/// Write the JSON for the graph if `LAYERED_CRATE_GRAPH_DIR` is set.
+///
+/// `errors` are the errors emitted by the macro, i.e. the validation result.
+/// Failing to write is not an error, since the output is only for tooling.
+pub(crate) fn write_graph(src_ident: &syn::Ident, graph: &DepsGraph, errors: &TokenStream2) {
+    let Some(dir) = std::env::var_os(GRAPH_DIR_ENV) else {
+        return;
+    };
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_default();
+    let parent_module = graph.parent_module();
+    let module = if parent_module.is_empty() {
+        src_ident.to_string()
+    } else {
+        format!("{parent_module}::{src_ident}")
+    };
+    let json = graph_to_json(&crate_name, &module, src_ident, graph, errors);
+    let mut out = String::new();
+    json.write(&mut out, 0);
+    out.push('\n');
+
+    let dir = std::path::PathBuf::from(dir);
+    let _ = std::fs::create_dir_all(&dir);
+    let file_name = format!("{crate_name}.{}.json", module.replace("::", "."));
+    let _ = std::fs::write(dir.join(file_name), out);
+}
+
+fn graph_to_json(
+    crate_name: &str,
+    module: &str,
+    src_ident: &syn::Ident,
+    graph: &DepsGraph,
+    errors: &TokenStream2,
+) -> Json {
+    let mut layers = graph.graph.values().collect::<Vec<_>>();
+    layers.sort_by_key(|entry| entry.order);
+    let allowed_cycles = layers
+        .iter()
+        .flat_map(|entry| entry.edges.iter().map(move |edge| (entry, edge)))
+        .filter_map(|(entry, edge)| {
+            let allow_cycle = edge.allow_cycle.as_ref()?;
+            Some(Json::Object(vec![
+                ("from", Json::String(entry.ident.to_string())),
+                ("to", Json::String(edge.name.clone())),
+                ("reason", Json::String(allow_cycle.reason.value())),
+                ("location", location(allow_cycle.ident.span())),
+            ]))
+        })
+        .collect();
+    let layers = layers
+        .into_iter()
+        .map(|entry| layer_to_json(graph, entry))
+        .collect();
+    let errors = error_messages(errors)
+        .into_iter()
+        .map(|(message, span)| {
+            let code = match message.strip_prefix('[').and_then(|x| x.split_once(']')) {
+                Some((code, _)) => Json::String(code.to_string()),
+                None => Json::Null,
+            };
+            Json::Object(vec![
+                ("code", code),
+                ("message", Json::String(message)),
+                ("location", location(span)),
+            ])
+        })
+        .collect();
+
+    Json::Object(vec![
+        ("version", Json::Number(SCHEMA_VERSION)),
+        ("crate", Json::String(crate_name.to_string())),
+        ("module", Json::String(module.to_string())),
+        ("location", location(src_ident.span())),
+        ("layers", Json::Array(layers)),
+        ("allowed_cycles", Json::Array(allowed_cycles)),
+        ("errors", Json::Array(errors)),
+    ])
+}
+
+fn layer_to_json(graph: &DepsGraph, entry: &ModuleDecl) -> Json {
+    let names = |names: Vec<String>| Json::Array(names.into_iter().map(Json::String).collect());
+    let edges = |edges: &[DepEdge]| Json::Array(edges.iter().map(edge_to_json).collect());
+    let docs = entry.doc_lines().collect::<Vec<_>>().join("\n");
+    let visible_to = match &entry.visible_to {
+        None => Json::Null,
+        Some(idents) => names(idents.iter().map(|x| x.to_string()).collect()),
+    };
+    let cfgs = entry
+        .cfgs
+        .iter()
+        .filter_map(|attr| attr.meta.require_list().ok())
+        .map(|list| list.tokens.to_string())
+        .collect();
+
+    Json::Object(vec![
+        ("name", Json::String(entry.ident.to_string())),
+        ("location", location(entry.ident.span())),
+        ("order", Json::Number(entry.order)),
+        ("public", Json::Bool(entry.is_pub)),
+        ("common", Json::Bool(entry.is_common)),
+        ("docs", Json::String(docs)),
+        (
+            "deprecated",
+            match &entry.deprecated {
+                None => Json::Null,
+                Some(attr) => Json::String(deprecation_note(attr)),
+            },
+        ),
+        ("cfg", names(cfgs)),
+        ("visible_to", visible_to),
+        (
+            "exports_macros",
+            names(
+                entry
+                    .exported_macros
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect(),
+            ),
+        ),
+        ("depends_on", edges(&entry.edges)),
+        ("reduced_depends_on", reduced_depends_on(graph, entry)),
+        ("dev_depends_on", edges(&entry.dev_edges)),
+        (
+            "common_deps",
+            names(entry.common_deps.iter().map(|x| x.to_string()).collect()),
+        ),
+        (
+            "used_by",
+            names(
+                graph
+                    .dependents(&entry.ident.to_string())
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+            ),
+        ),
+    ])
+}
+
+/// The dependencies that are left in the transitive reduction of the graph, i.e. not implied
+/// by another dependency (not counting `#[allow_cycle]` edges). `null` if the graph has cycles
+fn reduced_depends_on(graph: &DepsGraph, entry: &ModuleDecl) -> Json {
+    if graph.has_circular_deps {
+        return Json::Null;
+    }
+    Json::Array(
+        entry
+            .layered_edges()
+            .filter(|edge| graph.implied_path(entry, edge).is_none())
+            .map(|edge| Json::String(edge.name.clone()))
+            .collect(),
+    )
+}
+
+fn edge_to_json(edge: &DepEdge) -> Json {
+    let items = match &edge.items {
+        None => Json::Null,
+        Some(items) => Json::Array(items.iter().map(|x| Json::String(x.to_string())).collect()),
+    };
+    Json::Object(vec![
+        ("name", Json::String(edge.name.clone())),
+        ("items", items),
+        ("location", location(edge.ident.span())),
+        (
+            "deprecated",
+            match &edge.deprecated {
+                None => Json::Null,
+                Some(note) => Json::String(note.value()),
+            },
+        ),
+        (
+            "allow_cycle",
+            match &edge.allow_cycle {
+                None => Json::Null,
+                Some(x) => Json::String(x.reason.value()),
+            },
+        ),
+    ])
+}
+
+/// Get the note from `#[deprecated = "..."]` or `#[deprecated(note = "...")]`,
+/// or empty string if there is no note
+fn deprecation_note(attr: &syn::Attribute) -> String {
+    let lit = match &attr.meta {
+        syn::Meta::Path(_) => None,
+        syn::Meta::NameValue(nv) => match &nv.value {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) => Some(lit.clone()),
+            _ => None,
+        },
+        syn::Meta::List(_) => {
+            let mut note = None;
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("note") {
+                    note = Some(meta.value()?.parse::<syn::LitStr>()?);
+                } else {
+                    // skip `since = "..."`
+                    meta.value()?.parse::<syn::Lit>()?;
+                }
+                Ok(())
+            });
+            note
+        }
+    };
+    lit.map(|x| x.value()).unwrap_or_default()
+}
+
+/// `{ "file": ..., "line": ..., "column": ... }`, with 1-based line and column
+fn location(span: Span2) -> Json {
+    let span = span.unwrap();
+    Json::Object(vec![
+        ("file", Json::String(span.file())),
+        ("line", Json::Number(span.line())),
+        ("column", Json::Number(span.column())),
+    ])
+}
+
+/// Get the messages and spans out of the `compile_error!` invocations
+/// produced by `syn::Error::to_compile_error`
+fn error_messages(tokens: &TokenStream2) -> Vec<(String, Span2)> {
+    let mut messages = Vec::new();
+    let mut iter = tokens.clone().into_iter();
+    while let Some(tt) = iter.next() {
+        let TokenTree::Ident(ident) = tt else {
+            continue;
+        };
+        if ident != "compile_error" {
+            continue;
+        }
+        let Some(TokenTree::Punct(_)) = iter.next() else {
+            continue;
+        };
+        let Some(TokenTree::Group(group)) = iter.next() else {
+            continue;
+        };
+        if let Ok(lit) = syn::parse2::<syn::LitStr>(group.stream()) {
+            messages.push((lit.value(), ident.span()));
+        }
+    }
+    messages
+}
+
+/// Minimal JSON value, to not depend on serde
+enum Json {
+    Null,
+    Bool(bool),
+    Number(usize),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+    /// Write the value pretty-printed with 2-space indentation
+    fn write(&self, out: &mut String, indent: usize) {
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(x) => {
+                let _ = write!(out, "{x}");
+            }
+            Json::Number(x) => {
+                let _ = write!(out, "{x}");
+            }
+            Json::String(x) => write_string(out, x),
+            Json::Array(items) if items.is_empty() => out.push_str("[]"),
+            Json::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    newline(out, indent + 1);
+                    item.write(out, indent + 1);
+                }
+                newline(out, indent);
+                out.push(']');
+            }
+            Json::Object(fields) if fields.is_empty() => out.push_str("{}"),
+            Json::Object(fields) => {
+                out.push('{');
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    newline(out, indent + 1);
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.write(out, indent + 1);
+                }
+                newline(out, indent);
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn newline(out: &mut String, indent: usize) {
+    out.push('\n');
+    for _ in 0..indent {
+        out.push_str("  ");
+    }
+}
+
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -9,24 +9,214 @@ use quote::quote;
 use quote::quote_spanned;
 use syn::parse_macro_input;
 
+mod auto;
+mod dsl;
+mod json;
+
 /// See [`crate documentation`](crate)
 #[proc_macro_attribute]
-pub fn layers(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn layers(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as LayersArgs);
     let input = parse_macro_input!(input as syn::ItemMod);
-    match layered_crate_expand(input) {
+    match layered_crate_expand(args, input) {
+        Ok(expanded) => expanded,
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Function-like alternative to [`macro@layers`], without the `extern crate` placeholders.
+///
+/// ```ignore
+/// layered_crate::define_layers! {
+///     #[layers(check_redundant_deps)]
+///     mod src {
+///         /// My Public APIs
+///         api -> [sub_system_1];
+///
+///         #[visible_to(api)]
+///         pub sub_system_1 -> [utils::{helper}];
+///
+///         utils;
+///     }
+/// }
+/// ```
+///
+/// Each `name -> [deps]` line is the same as `#[depends_on(dep)]` on `extern crate name;`,
+/// other attributes on a layer are kept as-is. The optional `#[layers(...)]` attribute
+/// takes the same arguments as the attribute form. The output is identical to the
+/// attribute form.
+///
+/// (The macro can't be named `layers!`, since that name is taken by the attribute.)
+#[proc_macro]
+pub fn define_layers(input: TokenStream) -> TokenStream {
+    let dsl::LayersDsl { args, module } = parse_macro_input!(input as dsl::LayersDsl);
+    match layered_crate_expand(args, module) {
         Ok(expanded) => expanded,
         Err(err) => err.to_compile_error().into(),
     }
 }
 
-fn layered_crate_expand(input: syn::ItemMod) -> syn::Result<TokenStream> {
-    let (_, content) = match input.content {
+/// Mark a `pub` item inside a layer as internal to the layer.
+///
+/// This is meant for items in a direct submodule of a layer, that the layer
+/// re-exports with `pub use foo::*`. The item becomes `pub(super)`, so it's visible
+/// to the whole layer, but is no longer re-exported by the generated wrapper module,
+/// and dependents get a privacy error (E0603) when importing it through `crate_`.
+/// The note of the error points to the `#[internal]` attribute.
+///
+/// `pub(super)` is relative to the module the item is in, so an item nested deeper
+/// (like `foo::bar::item`) is only visible to `foo`. Use `pub(in crate::src::layer)`
+/// for those instead.
+///
+/// Items directly in the layer's own file don't need this - just make them private,
+/// since submodules can always see private items of their parent.
+#[proc_macro_attribute]
+pub fn internal(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut item = parse_macro_input!(input as syn::Item);
+    if !attr.is_empty() {
+        let attr = TokenStream2::from(attr);
+        let e = syn::Error::new_spanned(attr, "[LC0017] #[internal] does not take any arguments");
+        return e.to_compile_error().into();
+    }
+    let pub_span = match item_vis_mut(&mut item) {
+        None => {
+            let e = syn::Error::new_spanned(
+                &item,
+                "[LC0017] #[internal] is not supported on this item",
+            );
+            return e.to_compile_error().into();
+        }
+        Some(syn::Visibility::Public(pub_token)) => Some(pub_token.span),
+        Some(_) => None,
+    };
+    let Some(span) = pub_span else {
+        let e = syn::Error::new_spanned(
+            &item,
+            "[LC0017] #[internal] can only be used on `pub` items, other visibilities are already hidden from dependents",
+        );
+        return e.to_compile_error().into();
+    };
+    // mark the visibility as coming from the attribute, so the privacy error
+    // points to `#[internal]` when the item is used outside of the layer
+    let span = Span2::call_site().located_at(span);
+    if let Some(vis) = item_vis_mut(&mut item) {
+        *vis = syn::parse_quote_spanned! { span => pub(super) };
+    }
+
+    quote! { #item }.into()
+}
+
+fn item_vis_mut(item: &mut syn::Item) -> Option<&mut syn::Visibility> {
+    let vis = match item {
+        syn::Item::Const(x) => &mut x.vis,
+        syn::Item::Enum(x) => &mut x.vis,
+        syn::Item::ExternCrate(x) => &mut x.vis,
+        syn::Item::Fn(x) => &mut x.vis,
+        syn::Item::Mod(x) => &mut x.vis,
+        syn::Item::Static(x) => &mut x.vis,
+        syn::Item::Struct(x) => &mut x.vis,
+        syn::Item::Trait(x) => &mut x.vis,
+        syn::Item::TraitAlias(x) => &mut x.vis,
+        syn::Item::Type(x) => &mut x.vis,
+        syn::Item::Union(x) => &mut x.vis,
+        syn::Item::Use(x) => &mut x.vis,
+        _ => return None,
+    };
+    Some(vis)
+}
+
+/// Arguments of the `#[layers(...)]` attribute
+#[derive(Default)]
+struct LayersArgs {
+    /// Error on dependencies that are already implied by other dependencies
+    check_redundant_deps: bool,
+    /// Complexity limits for all modules
+    budget: Budget,
+    /// Generate a `__layers_<module>` module with an overview of the graph in the docs
+    doc_overview: bool,
+    /// Modules that are implicitly available to every other module
+    common: Vec<syn::Ident>,
+    /// Directory (relative to the package) to discover modules from
+    auto: Option<syn::LitStr>,
+    /// Path of the module containing the `#[layers]` module, if not the crate root
+    at: Option<syn::Path>,
+}
+
+impl syn::parse::Parse for LayersArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+        let metas =
+            syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated(input)?;
+        for meta in metas {
+            if meta.path().is_ident("check_redundant_deps") {
+                meta.require_path_only()?;
+                args.check_redundant_deps = true;
+                continue;
+            }
+            if meta.path().is_ident("doc_overview") {
+                meta.require_path_only()?;
+                args.doc_overview = true;
+                continue;
+            }
+            if meta.path().is_ident("common") {
+                let value = &meta.require_name_value()?.value;
+                let syn::Expr::Array(array) = value else {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "[LC0018] expected a list of modules, like `common = [utils, errors]`",
+                    ));
+                };
+                for elem in &array.elems {
+                    args.common
+                        .push(syn::parse2::<syn::Ident>(quote! { #elem })?);
+                }
+                continue;
+            }
+            if meta.path().is_ident("at") {
+                let value = &meta.require_name_value()?.value;
+                let path = syn::parse2::<syn::Path>(quote! { #value })?;
+                if path.leading_colon.is_some()
+                    || path.segments.first().is_none_or(|x| x.ident != "crate")
+                {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "[LC0018] expected a path starting with `crate`, like `at = crate::engine`",
+                    ));
+                }
+                args.at = Some(path);
+                continue;
+            }
+            if meta.path().is_ident("auto") {
+                let value = &meta.require_name_value()?.value;
+                args.auto = Some(syn::parse2::<syn::LitStr>(quote! { #value })?);
+                continue;
+            }
+            if let Some(limit) = args.budget.get_mut(meta.path()) {
+                let value = &meta.require_name_value()?.value;
+                let lit = syn::parse2::<syn::LitInt>(quote! { #value })?;
+                *limit = Some(lit.base10_parse()?);
+                continue;
+            }
+            return Err(syn::Error::new_spanned(
+                meta.path(),
+                "[LC0018] unknown argument for #[layers]",
+            ));
+        }
+        Ok(args)
+    }
+}
+
+fn layered_crate_expand(args: LayersArgs, input: syn::ItemMod) -> syn::Result<TokenStream> {
+    let (_, mut content) = match input.content {
         None => {
             // nothing in the mod
             return Ok(quote! { #input }.into());
         }
         Some(content) => content,
     };
+    if let Some(dir) = &args.auto {
+        content.extend(auto::discover_layers(dir)?);
+    }
 
     let mut before_tokens = TokenStream2::new();
     let mut has_doc_hidden = false;
@@ -34,6 +224,7 @@ fn layered_crate_expand(input: syn::ItemMod) -> syn::Result<TokenStream> {
     // keep the original attributes, except for the ones we don't want
     for attr in input.attrs {
         // skip #[doc(hidden)]
+        #[allow(clippy::collapsible_if)]
         if attr.path().is_ident("doc") {
             if let Ok(x) = attr
                 .meta
@@ -52,8 +243,16 @@ fn layered_crate_expand(input: syn::ItemMod) -> syn::Result<TokenStream> {
     }
 
     // collect the dependency attributes
-    let mut graph = DepsGraph::default();
-    let mut transformed_src_content = TokenStream2::new();
+    let mut graph = DepsGraph {
+        check_redundant_deps: args.check_redundant_deps,
+        budget: args.budget,
+        doc_overview: args.doc_overview,
+        common: args.common,
+        auto_order: args.auto.is_some(),
+        at: args.at,
+        ..Default::default()
+    };
+    let mut src_items = Vec::new();
     let mut error_tokens = TokenStream2::new();
 
     for item in content {
@@ -70,7 +269,7 @@ fn layered_crate_expand(input: syn::ItemMod) -> syn::Result<TokenStream> {
                 if let Some(rename) = item.rename {
                     let e = syn::Error::new_spanned(
                         &rename.1,
-                        "rename syntax (as ...) is not supported when using #[layers]",
+                        "[LC0019] rename syntax (as ...) is not supported when using #[layers]",
                     );
                     extra_tokens.extend(e.to_compile_error());
                 }
@@ -92,20 +291,20 @@ fn layered_crate_expand(input: syn::ItemMod) -> syn::Result<TokenStream> {
             }
             _ => {
                 // other items in the mod, we just leave them along
-                transformed_src_content.extend(quote! { #item });
+                src_items.push(SrcItem::Other(quote! { #item }));
                 continue;
             }
         };
 
         // Extract the attributes
-        let mut edges = Vec::with_capacity(attrs.len());
-        let mut docs = TokenStream2::new();
+        let mut decl = ModuleDecl::new(matches!(vis, syn::Visibility::Public(_)), ident);
         for attr in attrs {
-            if attr.path().is_ident("depends_on") {
-                let ident = match attr
+            let is_dev = attr.path().is_ident("dev_depends_on");
+            if is_dev || attr.path().is_ident("depends_on") {
+                let args = match attr
                     .meta
                     .require_list()
-                    .and_then(|m| m.parse_args::<syn::Ident>())
+                    .and_then(|m| m.parse_args::<DependsOnArgs>())
                 {
                     Ok(x) => x,
                     Err(e) => {
@@ -113,42 +312,152 @@ fn layered_crate_expand(input: syn::ItemMod) -> syn::Result<TokenStream> {
                         continue;
                     }
                 };
-                edges.push(DepEdge {
-                    name: ident.to_string(),
+                let edge = DepEdge {
+                    name: args.ident.to_string(),
                     attr,
-                    ident,
-                });
+                    ident: args.ident,
+                    items: args.items,
+                    deprecated: args.deprecated,
+                    allow_cycle: None,
+                    macros: Vec::new(),
+                };
+                if is_dev {
+                    decl.dev_edges.push(edge);
+                } else {
+                    decl.edges.push(edge);
+                }
+                continue;
+            }
+
+            if attr.path().is_ident("allow_cycle") {
+                match attr
+                    .meta
+                    .require_list()
+                    .and_then(|m| m.parse_args::<AllowCycleArgs>())
+                {
+                    Ok(x) => decl.allow_cycles.push((attr, x)),
+                    Err(e) => error_tokens.extend(e.to_compile_error()),
+                }
+                continue;
+            }
+
+            if attr.path().is_ident("visible_to") {
+                let idents = match attr.meta.require_list().and_then(|m| {
+                    m.parse_args_with(
+                        syn::punctuated::Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated,
+                    )
+                }) {
+                    Ok(x) if x.is_empty() => {
+                        let e = syn::Error::new_spanned(
+                            &attr,
+                            "[LC0008] #[visible_to] needs at least one module name",
+                        );
+                        error_tokens.extend(e.to_compile_error());
+                        continue;
+                    }
+                    Ok(x) => x,
+                    Err(e) => {
+                        error_tokens.extend(e.to_compile_error());
+                        continue;
+                    }
+                };
+                decl.visible_to.get_or_insert_default().extend(idents);
+                decl.visible_to_attr.get_or_insert(attr);
+                continue;
+            }
+
+            if attr.path().is_ident("exports_macros") {
+                match attr.meta.require_list().and_then(|m| {
+                    m.parse_args_with(
+                        syn::punctuated::Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated,
+                    )
+                }) {
+                    Ok(idents) if idents.is_empty() => error_tokens.extend(
+                        syn::Error::new_spanned(
+                            &attr,
+                            "[LC0024] #[exports_macros] needs at least one macro name",
+                        )
+                        .to_compile_error(),
+                    ),
+                    Ok(idents) => decl.exported_macros.extend(idents),
+                    Err(e) => error_tokens.extend(
+                        syn::Error::new(e.span(), format!("[LC0024] {e}")).to_compile_error(),
+                    ),
+                }
+                continue;
+            }
+
+            if attr.path().is_ident("common") {
+                if let Err(e) = attr.meta.require_path_only() {
+                    error_tokens.extend(e.to_compile_error());
+                }
+                decl.is_common = true;
+                continue;
+            }
+
+            if let Some(limit) = decl.budget.get_mut(attr.path()) {
+                match attr
+                    .meta
+                    .require_list()
+                    .and_then(|m| m.parse_args::<syn::LitInt>())
+                    .and_then(|x| x.base10_parse::<usize>())
+                {
+                    Ok(x) => *limit = Some(x),
+                    Err(e) => error_tokens.extend(e.to_compile_error()),
+                }
                 continue;
             }
 
             if attr.path().is_ident("doc") {
-                docs.extend(quote! { #attr });
+                decl.docs.push(attr.clone());
+            }
+            if attr.path().is_ident("deprecated") {
+                decl.deprecated = Some(attr.clone());
+            }
+            if attr.path().is_ident("cfg") {
+                decl.cfgs.push(attr.clone());
+            }
+            if attr.path().is_ident("macro_use") {
+                decl.macro_use = Some(attr.clone());
             }
 
             // keep attributes unrelated to us
-            transformed_src_content.extend(quote! { #attr });
+            decl.src_attrs.extend(quote! { #attr });
         }
 
-        transformed_src_content.extend(quote! {
+        let ident = &decl.ident;
+        decl.src_decl = quote! {
             pub mod #ident #extra_tokens
-        });
-        graph.add(
-            matches!(vis, syn::Visibility::Public(_)),
-            ident,
-            docs,
-            edges,
-        );
+        };
+        src_items.push(SrcItem::Layer(ident.to_string()));
+        graph.add(decl);
     }
 
     // check - this produces the errors as tokens instead of
     // result. we still emit the expanded output even if check fails,
     // so that we don't cause massive compile failures
-    error_tokens.extend(graph.check());
+    error_tokens.extend(graph.check(&input.ident));
+    json::write_graph(&input.ident, &graph, &error_tokens);
 
     // create a new ident, so unused warnings don't show up
     // on the entire macro input
     let src_ident = syn::Ident::new(&input.ident.to_string(), Span2::call_site());
     let mod_tokens = graph.generate_impl(&src_ident);
+    let mut transformed_src_content = TokenStream2::new();
+    let layer_order = src_items
+        .iter()
+        .filter_map(|x| match x {
+            SrcItem::Layer(name) => Some(name.clone()),
+            SrcItem::Other(_) => None,
+        })
+        .collect::<Vec<_>>();
+    transformed_src_content.extend(graph.generate_macro_use_warnings(&layer_order));
+    for item in src_items {
+        match item {
+            SrcItem::Other(tokens) => transformed_src_content.extend(tokens),
+            SrcItem::Layer(name) => transformed_src_content.extend(graph.generate_src_mod(&name)),
+        }
+    }
 
     let expanded = quote! {
         #before_tokens
@@ -162,10 +471,28 @@ fn layered_crate_expand(input: syn::ItemMod) -> syn::Result<TokenStream> {
     Ok(expanded.into())
 }
 
+/// An item in the transformed `#[layers]` module, in the original order
+enum SrcItem {
+    /// Item that's not a module, kept as is
+    Other(TokenStream2),
+    /// A module declaration, by name
+    Layer(String),
+}
+
 #[derive(Default)]
 struct DepsGraph {
     graph: BTreeMap<String, ModuleDecl>,
     has_circular_deps: bool,
+    check_redundant_deps: bool,
+    budget: Budget,
+    doc_overview: bool,
+    /// Modules listed in `#[layers(common = [...])]`
+    common: Vec<syn::Ident>,
+    /// Modules are discovered with `#[layers(auto = "...")]`, so the order
+    /// comes from the graph instead of the source
+    auto_order: bool,
+    /// Path from `#[layers(at = ...)]`, `None` for the crate root
+    at: Option<syn::Path>,
 }
 
 struct ModuleDecl {
@@ -173,12 +500,114 @@ struct ModuleDecl {
     order: usize,
     /// Whether the mod has `pub`
     is_pub: bool,
+    /// Whether the mod is available to every other mod,
+    /// from `#[common]` or `#[layers(common = [...])]`
+    is_common: bool,
     /// Ident for the mod
     ident: syn::Ident,
     /// Doc attributes for this mod
-    docs: TokenStream2,
+    docs: Vec<syn::Attribute>,
+    /// `#[cfg]` attributes for this mod, also applied to the generated wrapper
+    cfgs: Vec<syn::Attribute>,
+    /// Attributes to keep on the mod, including docs
+    src_attrs: TokenStream2,
+    /// The `pub mod` declaration to put in the `#[layers]` module
+    src_decl: TokenStream2,
+    /// The only modules allowed to depend on this mod, from `#[visible_to]`.
+    /// `None` means every module can depend on it
+    visible_to: Option<Vec<syn::Ident>>,
+    /// The first `#[visible_to]` attribute, for errors about the whole list
+    visible_to_attr: Option<syn::Attribute>,
+    /// Complexity limits that override the ones in `#[layers]`
+    budget: Budget,
     /// Dependencies
     edges: Vec<DepEdge>,
+    /// Dependencies only available in tests, from `#[dev_depends_on]`.
+    ///
+    /// These are not part of the graph when checking for circular deps and order
+    dev_edges: Vec<DepEdge>,
+    /// `#[allow_cycle]` attributes, matched to `edges` when checking
+    allow_cycles: Vec<(syn::Attribute, AllowCycleArgs)>,
+    /// Common modules implicitly available to this mod. Since common mods
+    /// can only depend on other common mods, these are also not part of the graph
+    common_deps: Vec<syn::Ident>,
+    /// Macros from `#[exports_macros]` of the common modules, with the module
+    /// they are from, filled in when checking the graph
+    common_macros: Vec<(syn::Ident, syn::Ident)>,
+    /// The `#[deprecated]` attribute of this mod, which is also
+    /// kept on the original mod so edges to it are warned
+    deprecated: Option<syn::Attribute>,
+    /// Macros from `#[exports_macros]`, re-exported to the dependents
+    exported_macros: Vec<syn::Ident>,
+    /// The `#[macro_use]` attribute of this mod, which is kept on the original mod
+    macro_use: Option<syn::Attribute>,
+}
+
+impl ModuleDecl {
+    fn new(is_pub: bool, ident: syn::Ident) -> Self {
+        Self {
+            order: 0,
+            is_pub,
+            is_common: false,
+            ident,
+            docs: Vec::new(),
+            cfgs: Vec::new(),
+            src_attrs: TokenStream2::new(),
+            src_decl: TokenStream2::new(),
+            visible_to: None,
+            visible_to_attr: None,
+            budget: Budget::default(),
+            edges: Vec::new(),
+            dev_edges: Vec::new(),
+            allow_cycles: Vec::new(),
+            common_deps: Vec::new(),
+            common_macros: Vec::new(),
+            deprecated: None,
+            exported_macros: Vec::new(),
+            macro_use: None,
+        }
+    }
+
+    /// The dependencies that are part of the layering, i.e. not including
+    /// the ones with `#[allow_cycle]`
+    fn layered_edges(&self) -> impl Iterator<Item = &DepEdge> {
+        self.edges.iter().filter(|x| x.allow_cycle.is_none())
+    }
+}
+
+/// Complexity limits of the graph. `None` means no limit
+#[derive(Default, Clone, Copy)]
+struct Budget {
+    /// Max number of modules in a dependency chain, including both ends
+    max_depth: Option<usize>,
+    /// Max number of direct dependencies of a module
+    max_fan_out: Option<usize>,
+    /// Max number of modules directly depending on a module
+    max_fan_in: Option<usize>,
+}
+
+impl Budget {
+    /// Get the limit by the name used in the attributes
+    fn get_mut(&mut self, path: &syn::Path) -> Option<&mut Option<usize>> {
+        if path.is_ident("max_depth") {
+            Some(&mut self.max_depth)
+        } else if path.is_ident("max_fan_out") {
+            Some(&mut self.max_fan_out)
+        } else if path.is_ident("max_fan_in") {
+            Some(&mut self.max_fan_in)
+        } else {
+            None
+        }
+    }
+
+    /// Use the limits in self, falling back to the ones in other
+    fn or(self, other: Budget) -> Budget {
+        Budget {
+            max_depth: self.max_depth.or(other.max_depth),
+            max_fan_out: self.max_fan_out.or(other.max_fan_out),
+            max_fan_in: self.max_fan_in.or(other.max_fan_in),
+        }
+    }
 }
 
 struct DepEdge {
@@ -188,32 +617,152 @@ struct DepEdge {
     ident: syn::Ident,
     /// The name of the dependency module
     name: String,
+    /// The only items used from the dependency, if specified with
+    /// `#[depends_on(module::{a, b})]`
+    items: Option<Vec<syn::Ident>>,
+    /// Note from `#[depends_on(module, deprecated = "...")]`, if this edge is deprecated
+    deprecated: Option<syn::LitStr>,
+    /// `#[allow_cycle(module, reason = "...")]`, if this edge is allowed
+    /// to form a cycle with other allowed edges. These edges are ignored when
+    /// checking the layering, except for cycles
+    allow_cycle: Option<AllowCycleArgs>,
+    /// Macros exported by the dependency with `#[exports_macros]`,
+    /// filled in when checking the graph
+    macros: Vec<syn::Ident>,
+}
+
+/// Arguments of the `#[allow_cycle(x, reason = "...")]` attribute
+struct AllowCycleArgs {
+    ident: syn::Ident,
+    reason: syn::LitStr,
+}
+
+impl syn::parse::Parse for AllowCycleArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+        let missing_reason = || {
+            syn::Error::new_spanned(
+                &ident,
+                format!(
+                    "[LC0021] #[allow_cycle] needs a reason, like #[allow_cycle({}, reason = \"...\")]",
+                    ident
+                ),
+            )
+        };
+        if input.is_empty() {
+            return Err(missing_reason());
+        }
+        input.parse::<syn::Token![,]>()?;
+        let key = input.parse::<syn::Ident>()?;
+        if key != "reason" {
+            return Err(syn::Error::new_spanned(
+                key,
+                format!(
+                    "[LC0021] expected `reason`, like #[allow_cycle({}, reason = \"...\")]",
+                    ident
+                ),
+            ));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let reason = input.parse::<syn::LitStr>()?;
+        if reason.value().trim().is_empty() {
+            return Err(missing_reason());
+        }
+        Ok(Self { ident, reason })
+    }
+}
+
+/// Arguments of the `#[depends_on(...)]` attribute
+///
+/// ```text
+/// #[depends_on(module)]
+/// #[depends_on(module::item)]
+/// #[depends_on(module::{item1, item2})]
+/// ```
+struct DependsOnArgs {
+    ident: syn::Ident,
+    items: Option<Vec<syn::Ident>>,
+    /// Note from `#[depends_on(module, deprecated = "...")]`
+    deprecated: Option<syn::LitStr>,
+}
+
+impl syn::parse::Parse for DependsOnArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+        let mut items = None;
+        if input.peek(syn::Token![::]) {
+            input.parse::<syn::Token![::]>()?;
+            if input.peek(syn::token::Brace) {
+                let content;
+                let brace = syn::braced!(content in input);
+                let list =
+                    syn::punctuated::Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated(
+                        &content,
+                    )?;
+                if list.is_empty() {
+                    return Err(syn::Error::new(
+                        brace.span.join(),
+                        format!(
+                            "[LC0026] expected at least one item, like #[depends_on({}::{{item}})]",
+                            ident
+                        ),
+                    ));
+                }
+                items = Some(list.into_iter().collect());
+            } else {
+                items = Some(vec![input.parse::<syn::Ident>()?]);
+            }
+        }
+        // `, deprecated = "..."`. Check ahead, since this is also
+        // parsed in a comma-separated list by `define_layers!`
+        let fork = input.fork();
+        let mut deprecated = None;
+        if fork.parse::<syn::Token![,]>().is_ok()
+            && fork.parse::<syn::Ident>().is_ok_and(|x| x == "deprecated")
+        {
+            input.parse::<syn::Token![,]>()?;
+            input.parse::<syn::Ident>()?;
+            input.parse::<syn::Token![=]>()?;
+            deprecated = Some(input.parse::<syn::LitStr>()?);
+        }
+        Ok(Self {
+            ident,
+            items,
+            deprecated,
+        })
+    }
 }
 
 impl DepsGraph {
-    fn add(&mut self, is_pub: bool, ident: syn::Ident, docs: TokenStream2, edges: Vec<DepEdge>) {
-        let order = self.graph.len();
-        self.graph.insert(
-            ident.to_string(),
-            ModuleDecl {
-                order,
-                is_pub,
-                ident,
-                docs,
-                edges,
-            },
-        );
+    fn add(&mut self, mut decl: ModuleDecl) {
+        decl.order = self.graph.len();
+        self.graph.insert(decl.ident.to_string(), decl);
     }
 
-    fn check(&mut self) -> TokenStream2 {
+    fn check(&mut self, src_ident: &syn::Ident) -> TokenStream2 {
         let mut tokens = TokenStream2::new();
         self.check_exists(&mut tokens);
+        self.check_duplicates(&mut tokens);
+        self.check_common(&mut tokens);
+        self.resolve_exported_macros();
+        self.check_visible_to(&mut tokens);
+        self.check_allow_cycles(&mut tokens);
         let circular_deps_result = self.check_circular_deps();
         if circular_deps_result.is_ok() {
+            self.check_unnecessary_allow_cycles(&mut tokens);
             // only check order if no circular deps,
             // because it's impossible to have the right order
             // if there are circular deps
-            self.check_attr_order(&mut tokens);
+            if self.auto_order {
+                self.sort_by_deps();
+            } else {
+                self.check_attr_order(&mut tokens);
+                self.suggest_order(src_ident, &mut tokens);
+            }
+            if self.check_redundant_deps {
+                self.check_redundant_deps(&mut tokens);
+            }
+            self.check_budget(&mut tokens);
         } else {
             self.has_circular_deps = true;
         }
@@ -227,23 +776,291 @@ impl DepsGraph {
     fn check_exists(&mut self, errors: &mut TokenStream2) {
         let keys = self.graph.keys().cloned().collect::<BTreeSet<_>>();
         for entry in self.graph.values_mut() {
-            let edges = {
-                let mut edges = Vec::with_capacity(entry.edges.len());
-                std::mem::swap(&mut entry.edges, &mut edges);
-                edges
+            for entry_edges in [&mut entry.edges, &mut entry.dev_edges] {
+                let edges = std::mem::take(entry_edges);
+                for edge in edges {
+                    if keys.contains(&edge.name) {
+                        entry_edges.push(edge);
+                        continue;
+                    }
+                    let e = syn::Error::new_spanned(
+                        &edge.attr,
+                        format!("[LC0001] cannot find dependency: {}", edge.name),
+                    )
+                    .to_compile_error();
+                    errors.extend(e);
+                    // don't add the bad dependency to the graph
+                }
+            }
+        }
+    }
+
+    // this is mut because we want to remove the duplicated dependencies,
+    // to prevent the re-exports from conflicting
+    fn check_duplicates(&mut self, errors: &mut TokenStream2) {
+        let mut seen = BTreeSet::new();
+        for entry in self.graph.values_mut() {
+            seen.clear();
+            for entry_edges in [&mut entry.edges, &mut entry.dev_edges] {
+                let edges = std::mem::take(entry_edges);
+                for edge in edges {
+                    if seen.insert(edge.name.clone()) {
+                        entry_edges.push(edge);
+                        continue;
+                    }
+                    let message = if edge.items.is_some() {
+                        format!(
+                            "[LC0006] duplicated dependency: {}, list all items in one #[depends_on({}::{{...}})] instead",
+                            edge.name, edge.name
+                        )
+                    } else {
+                        format!("[LC0006] duplicated dependency: {}", edge.name)
+                    };
+                    let e = syn::Error::new_spanned(&edge.attr, message).to_compile_error();
+                    errors.extend(e);
+                }
+            }
+        }
+    }
+
+    /// Mark the common modules, and make sure they only depend on other
+    /// common modules, so the implicit dependencies can't create cycles
+    fn check_common(&mut self, errors: &mut TokenStream2) {
+        for ident in &self.common {
+            match self.graph.get_mut(&ident.to_string()) {
+                Some(entry) => entry.is_common = true,
+                None => {
+                    let e = syn::Error::new_spanned(
+                        ident,
+                        format!("[LC0008] cannot find module: {}", ident),
+                    )
+                    .to_compile_error();
+                    errors.extend(e);
+                }
+            }
+        }
+        let mut common = self
+            .graph
+            .values()
+            .filter(|x| x.is_common)
+            .map(|x| (x.order, x.ident.clone()))
+            .collect::<Vec<_>>();
+        if common.is_empty() {
+            return;
+        }
+        common.sort_by_key(|(order, _)| *order);
+        let common = common.into_iter().map(|(_, x)| x).collect::<Vec<_>>();
+        for (name, entry) in &self.graph {
+            if entry.is_common
+                && let Some(attr) = &entry.visible_to_attr
+            {
+                let e = syn::Error::new_spanned(
+                    attr,
+                    format!(
+                        "[LC0013] common module `{}` is available to every module, and cannot have #[visible_to]",
+                        name
+                    ),
+                )
+                .to_compile_error();
+                errors.extend(e);
+            }
+            for edge in entry.edges.iter().chain(&entry.dev_edges) {
+                let Some(m) = self.graph.get(&edge.name) else {
+                    continue;
+                };
+                if entry.is_common && !m.is_common {
+                    let e = syn::Error::new_spanned(
+                        &edge.attr,
+                        format!(
+                            "[LC0014] common module `{}` cannot depend on `{}`, which is not common",
+                            name, edge.name
+                        ),
+                    )
+                    .to_compile_error();
+                    errors.extend(e);
+                }
+                if !entry.is_common && m.is_common {
+                    let e = syn::Error::new_spanned(
+                        &edge.attr,
+                        format!(
+                            "[LC0015] unnecessary dependency: `{}` is a common module and is already available to every module",
+                            edge.name
+                        ),
+                    )
+                    .to_compile_error();
+                    errors.extend(e);
+                }
+            }
+        }
+        for entry in self.graph.values_mut() {
+            if entry.is_common {
+                continue;
+            }
+            // remove the explicit dependencies on common modules,
+            // so they don't conflict with the implicit ones
+            let is_common = |edge: &DepEdge| common.iter().any(|x| x == &edge.name);
+            entry.edges.retain(|x| !is_common(x));
+            entry.dev_edges.retain(|x| !is_common(x));
+            entry.common_deps = common.clone();
+        }
+    }
+
+    /// Copy the `#[exports_macros]` of each module to the edges pointing to it
+    fn resolve_exported_macros(&mut self) {
+        let exported = self
+            .graph
+            .iter()
+            .map(|(name, entry)| (name.clone(), entry.exported_macros.clone()))
+            .collect::<BTreeMap<_, _>>();
+        for entry in self.graph.values_mut() {
+            for edge in entry.edges.iter_mut().chain(&mut entry.dev_edges) {
+                if let Some(macros) = exported.get(&edge.name) {
+                    edge.macros = macros.clone();
+                }
+            }
+            for common_ident in &entry.common_deps {
+                for mac in exported
+                    .get(&common_ident.to_string())
+                    .into_iter()
+                    .flatten()
+                {
+                    entry
+                        .common_macros
+                        .push((common_ident.clone(), mac.clone()));
+                }
+            }
+        }
+    }
+
+    /// Warn about modules with `#[macro_use]`, whose macros are textually visible
+    /// to every module declared after them in `order`, including the ones that
+    /// don't depend on them.
+    ///
+    /// Proc macros can't emit warnings, so this uses a deprecated constant
+    fn generate_macro_use_warnings(&self, order: &[String]) -> TokenStream2 {
+        let mut tokens = TokenStream2::new();
+        for (i, name) in order.iter().enumerate() {
+            let Some(entry) = self.graph.get(name) else {
+                continue;
+            };
+            let Some(attr) = &entry.macro_use else {
+                continue;
             };
-            for edge in edges {
-                if keys.contains(&edge.name) {
-                    entry.edges.push(edge);
+            let leaked_to = order[i + 1..]
+                .iter()
+                .filter_map(|x| self.graph.get(x))
+                .filter(|x| {
+                    !x.edges
+                        .iter()
+                        .chain(&x.dev_edges)
+                        .any(|edge| &edge.name == name)
+                })
+                .map(|x| format!("`{}`", x.ident))
+                .collect::<Vec<_>>();
+            if leaked_to.is_empty() {
+                continue;
+            }
+            let note = format!(
+                "[LC0025] macros from `{}` are visible to {} through #[macro_use], \
+                 but they don't depend on it. Use #[exports_macros] instead",
+                name,
+                leaked_to.join(", ")
+            );
+            let span = attr.path().segments[0].ident.span();
+            let warning = syn::Ident::new(&format!("__macro_use_{name}"), span);
+            tokens.extend(quote_spanned! {
+                span =>
+                    #[deprecated(note = #note)]
+                    #[allow(non_upper_case_globals)]
+                    const #warning: () = ();
+                    const _: () = #warning;
+            });
+        }
+        tokens
+    }
+
+    /// Make sure modules with #[visible_to] are only depended on
+    /// by the modules listed
+    fn check_visible_to(&self, errors: &mut TokenStream2) {
+        for (name, entry) in &self.graph {
+            for ident in entry.visible_to.iter().flatten() {
+                if !self.graph.contains_key(&ident.to_string()) {
+                    let e = syn::Error::new_spanned(
+                        ident,
+                        format!("[LC0008] cannot find module: {}", ident),
+                    )
+                    .to_compile_error();
+                    errors.extend(e);
+                }
+            }
+            for edge in entry.edges.iter().chain(&entry.dev_edges) {
+                let Some(m) = self.graph.get(&edge.name) else {
+                    continue;
+                };
+                let Some(visible_to) = &m.visible_to else {
+                    continue;
+                };
+                if visible_to.iter().any(|x| x == name) {
                     continue;
                 }
                 let e = syn::Error::new_spanned(
                     &edge.attr,
-                    format!("cannot find dependency: {}", edge.name),
+                    format!(
+                        "[LC0007] module `{}` cannot be depended on by `{}`, because it is only visible to {}",
+                        edge.name,
+                        name,
+                        format_ident_list(visible_to)
+                    ),
+                )
+                .to_compile_error();
+                errors.extend(e);
+            }
+        }
+    }
+
+    /// Mark the edges named by `#[allow_cycle]`, so they are left out of the layering.
+    /// They are still checked for cycles in [`Self::check_circular_deps`]
+    fn check_allow_cycles(&mut self, errors: &mut TokenStream2) {
+        for entry in self.graph.values_mut() {
+            for (attr, args) in std::mem::take(&mut entry.allow_cycles) {
+                let Some(edge) = entry.edges.iter_mut().find(|x| args.ident == x.name) else {
+                    let e = syn::Error::new_spanned(
+                        &attr,
+                        format!(
+                            "[LC0021] #[allow_cycle({})] needs a matching #[depends_on({})]",
+                            args.ident, args.ident
+                        ),
+                    )
+                    .to_compile_error();
+                    errors.extend(e);
+                    continue;
+                };
+                edge.allow_cycle = Some(args);
+            }
+        }
+    }
+
+    /// Check that every `#[allow_cycle]` edge actually closes a cycle.
+    ///
+    /// Must only be called when there are no circular dependencies
+    fn check_unnecessary_allow_cycles(&self, errors: &mut TokenStream2) {
+        for (name, entry) in &self.graph {
+            for edge in &entry.edges {
+                let Some(allow_cycle) = &edge.allow_cycle else {
+                    continue;
+                };
+                if self.find_path_with_allowed(&edge.name, name).is_some() {
+                    continue;
+                }
+                let e = syn::Error::new_spanned(
+                    &allow_cycle.ident,
+                    format!(
+                        "[LC0022] unnecessary #[allow_cycle({})]: `{}` does not depend on `{}`, so there is no cycle",
+                        edge.name, edge.name, name
+                    ),
                 )
                 .to_compile_error();
                 errors.extend(e);
-                // don't add the bad dependency to the graph
             }
         }
     }
@@ -254,6 +1071,23 @@ impl DepsGraph {
             let mut stack = vec![name.clone()];
             self.check_circular_deps_recur(name, &entry.ident, &mut stack, &mut checked)?;
         }
+        // `#[allow_cycle]` only allows cycles where every edge has it,
+        // so a cycle through an allowed edge is still an error if it has
+        // any other edge
+        for (name, entry) in self.graph.iter() {
+            for edge in entry.layered_edges() {
+                if let Some(path) = self.find_path_with_allowed(&edge.name, name) {
+                    let graph = format!("{} -> {}", name, path.join(" -> "));
+                    return Err(syn::Error::new_spanned(
+                        &edge.attr,
+                        format!(
+                            "[LC0002] circular dependency detected: {}. #[allow_cycle] only allows a cycle if every dependency in it has #[allow_cycle]",
+                            graph
+                        ),
+                    ));
+                }
+            }
+        }
         Ok(())
     }
 
@@ -271,16 +1105,16 @@ impl DepsGraph {
         let Some(entry) = self.graph.get(name) else {
             return Err(syn::Error::new_spanned(
                 ident,
-                format!("cannot find dependency: {}", name),
+                format!("[LC0001] cannot find dependency: {}", name),
             ));
         };
 
-        for edge in &entry.edges {
+        for edge in entry.layered_edges() {
             if stack.contains(&edge.name) {
                 let graph = format_stack(stack, &edge.name);
                 return Err(syn::Error::new_spanned(
                     &edge.attr,
-                    format!("circular dependency detected: {}", graph),
+                    format!("[LC0002] circular dependency detected: {}", graph),
                 ));
             }
             stack.push(edge.name.clone());
@@ -298,7 +1132,19 @@ impl DepsGraph {
         for (name, entry) in &self.graph {
             orders.clear();
             let mut current_dep_order = 0;
-            for dep in &entry.edges {
+            // the whole sorted list, so all the attributes can be fixed at once
+            let mut sorted_deps = entry
+                .layered_edges()
+                .filter_map(|x| self.graph.get(&x.name))
+                .map(|x| (x.order, format!("#[depends_on({})]", x.ident)))
+                .collect::<Vec<_>>();
+            sorted_deps.sort();
+            let sorted_deps = sorted_deps
+                .into_iter()
+                .map(|(_, x)| x)
+                .collect::<Vec<_>>()
+                .join(", ");
+            for dep in entry.layered_edges() {
                 let Some(m) = self.graph.get(&dep.name) else {
                     continue;
                 };
@@ -306,7 +1152,7 @@ impl DepsGraph {
                     let e = syn::Error::new_spanned(
                         &entry.ident,
                         format!(
-                            "module `{}` should be declared before its dependency `{}` to ensure top-down readability",
+                            "[LC0003] module `{}` should be declared before its dependency `{}` to ensure top-down readability",
                             name, dep.name
                         ),
                     ).to_compile_error();
@@ -320,8 +1166,8 @@ impl DepsGraph {
                             let e = syn::Error::new_spanned(
                                 &dep.ident,
                                 format!(
-                                    "#[depends_on({})] should be before #[depends_on({})] to ensure consistent order of modules",
-                                    dep.name, n
+                                    "[LC0004] #[depends_on({})] should be before #[depends_on({})] to ensure consistent order of modules. The sorted order is: {}",
+                                    dep.name, n, sorted_deps
                                 ),
                             ).to_compile_error();
                             errors.extend(e);
@@ -335,8 +1181,8 @@ impl DepsGraph {
                         let e = syn::Error::new_spanned(
                             &dep.ident,
                             format!(
-                                "#[depends_on({})] should be placed in the same order the modules are declared",
-                                dep.name
+                                "[LC0004] #[depends_on({})] should be placed in the same order the modules are declared. The sorted order is: {}",
+                                dep.name, sorted_deps
                             ),
                         ).to_compile_error();
                         errors.extend(e);
@@ -349,35 +1195,589 @@ impl DepsGraph {
         }
     }
 
+    /// If the modules are not declared top-down, suggest an order
+    /// that is, so the whole block can be fixed at once
+    fn suggest_order(&self, src_ident: &syn::Ident, errors: &mut TokenStream2) {
+        let sorted = self.sorted_names();
+        let is_sorted = sorted
+            .iter()
+            .enumerate()
+            .all(|(i, name)| self.graph.get(*name).is_some_and(|x| x.order == i));
+        if is_sorted {
+            return;
+        }
+        let e = syn::Error::new_spanned(
+            src_ident,
+            format!(
+                "[LC0005] modules should be declared in this order: {}",
+                sorted.join(", ")
+            ),
+        )
+        .to_compile_error();
+        errors.extend(e);
+    }
+
+    /// Get the module names in an order where every module is before its dependencies.
+    /// Ties are broken by the declaration order, so an already valid order is unchanged.
+    ///
+    /// Must only be called when there are no circular dependencies
+    fn sorted_names(&self) -> Vec<&str> {
+        // number of dependents not yet in the output
+        let mut remaining = self
+            .graph
+            .keys()
+            .map(|name| (name.as_str(), 0usize))
+            .collect::<BTreeMap<_, _>>();
+        for entry in self.graph.values() {
+            for edge in entry.layered_edges() {
+                if let Some(count) = remaining.get_mut(edge.name.as_str()) {
+                    *count += 1;
+                }
+            }
+        }
+        let mut sorted = Vec::with_capacity(self.graph.len());
+        while let Some(name) = remaining
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(name, _)| *name)
+            .min_by_key(|name| self.graph[*name].order)
+        {
+            remaining.remove(name);
+            for edge in self.graph[name].layered_edges() {
+                if let Some(count) = remaining.get_mut(edge.name.as_str()) {
+                    *count -= 1;
+                }
+            }
+            sorted.push(name);
+        }
+        sorted
+    }
+
+    /// Re-number the modules in dependency order, and sort the edges of each module
+    /// by that order. Used instead of the order checks when the order doesn't come from the source
+    fn sort_by_deps(&mut self) {
+        let sorted = self
+            .sorted_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        for (order, name) in sorted.iter().enumerate() {
+            if let Some(entry) = self.graph.get_mut(name) {
+                entry.order = order;
+            }
+        }
+        let orders = self
+            .graph
+            .iter()
+            .map(|(name, entry)| (name.clone(), entry.order))
+            .collect::<BTreeMap<_, _>>();
+        for entry in self.graph.values_mut() {
+            entry.edges.sort_by_key(|edge| orders[&edge.name]);
+            entry.dev_edges.sort_by_key(|edge| orders[&edge.name]);
+        }
+    }
+
+    /// Check for dependencies that are already implied by
+    /// another dependency, i.e. `a -> c` when there is also `a -> b -> c`
+    fn check_redundant_deps(&self, errors: &mut TokenStream2) {
+        for (name, entry) in &self.graph {
+            for edge in entry.layered_edges() {
+                let Some(path) = self.implied_path(entry, edge) else {
+                    continue;
+                };
+                let e = syn::Error::new_spanned(
+                    &edge.attr,
+                    format!(
+                        "[LC0009] redundant dependency `{}`, already implied by: {} -> {}",
+                        edge.name,
+                        name,
+                        path.join(" -> ")
+                    ),
+                )
+                .to_compile_error();
+                errors.extend(e);
+            }
+        }
+    }
+
+    /// Check the depth, fan-out and fan-in limits of each module
+    fn check_budget(&self, errors: &mut TokenStream2) {
+        let chain_lengths = self.chain_lengths();
+        for (name, entry) in &self.graph {
+            let budget = entry.budget.or(self.budget);
+            if let Some(max_depth) = budget.max_depth {
+                let chain = self.longest_chain(name, &chain_lengths);
+                if chain.len() > max_depth {
+                    let e = syn::Error::new_spanned(
+                        &entry.ident,
+                        format!(
+                            "[LC0010] dependency chain of `{}` is too deep ({} > max_depth = {}): {}",
+                            name,
+                            chain.len(),
+                            max_depth,
+                            chain.join(" -> ")
+                        ),
+                    )
+                    .to_compile_error();
+                    errors.extend(e);
+                }
+            }
+            if let Some(max_fan_out) = budget.max_fan_out
+                && let Some(first_over) = entry.edges.get(max_fan_out)
+            {
+                let e = syn::Error::new_spanned(
+                    &first_over.attr,
+                    format!(
+                        "[LC0011] `{}` has too many dependencies ({} > max_fan_out = {}), over the limit: {}",
+                        name,
+                        entry.edges.len(),
+                        max_fan_out,
+                        entry.edges[max_fan_out..]
+                            .iter()
+                            .map(|x| format!("`{}`", x.name))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                )
+                .to_compile_error();
+                errors.extend(e);
+            }
+            if let Some(max_fan_in) = budget.max_fan_in {
+                let dependents = self.dependents(name);
+                if dependents.len() > max_fan_in {
+                    let e = syn::Error::new_spanned(
+                        &entry.ident,
+                        format!(
+                            "[LC0012] `{}` has too many dependents ({} > max_fan_in = {}): {}",
+                            name,
+                            dependents.len(),
+                            max_fan_in,
+                            dependents
+                                .iter()
+                                .map(|x| format!("`{}`", x))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    )
+                    .to_compile_error();
+                    errors.extend(e);
+                }
+            }
+        }
+    }
+
+    /// Get the length of the longest dependency chain starting from each module,
+    /// including itself.
+    ///
+    /// Must only be called when there are no circular dependencies
+    fn chain_lengths(&self) -> BTreeMap<&str, usize> {
+        let mut lengths = BTreeMap::new();
+        for name in self.graph.keys() {
+            self.chain_length(name, &mut lengths);
+        }
+        lengths
+    }
+
+    fn chain_length<'a>(&'a self, name: &'a str, lengths: &mut BTreeMap<&'a str, usize>) -> usize {
+        if let Some(length) = lengths.get(name) {
+            return *length;
+        }
+        let mut longest = 0;
+        if let Some(entry) = self.graph.get(name) {
+            for edge in entry.layered_edges() {
+                longest = longest.max(self.chain_length(&edge.name, lengths));
+            }
+        }
+        lengths.insert(name, longest + 1);
+        longest + 1
+    }
+
+    /// Get the longest dependency chain starting from `name`, including itself,
+    /// using the lengths from [`Self::chain_lengths`]
+    fn longest_chain(&self, name: &str, chain_lengths: &BTreeMap<&str, usize>) -> Vec<String> {
+        let mut chain = vec![name.to_owned()];
+        let mut current = name;
+        while let Some(entry) = self.graph.get(current) {
+            // the first dependency with the longest chain
+            let mut next = None::<(&str, usize)>;
+            for edge in entry.layered_edges() {
+                let length = chain_lengths.get(edge.name.as_str()).copied().unwrap_or(1);
+                if next.is_none_or(|(_, x)| length > x) {
+                    next = Some((&edge.name, length));
+                }
+            }
+            let Some((name, _)) = next else {
+                break;
+            };
+            chain.push(name.to_owned());
+            current = name;
+        }
+        chain
+    }
+
+    /// Get the names of the modules that directly depend on `name`,
+    /// in declaration order
+    fn dependents(&self, name: &str) -> Vec<&str> {
+        let mut dependents = self
+            .graph
+            .iter()
+            .filter(|(_, entry)| entry.edges.iter().any(|x| x.name == name))
+            .map(|(n, entry)| (entry.order, n.as_str()))
+            .collect::<Vec<_>>();
+        dependents.sort();
+        dependents.into_iter().map(|(_, n)| n).collect()
+    }
+
+    /// Whether other modules use `name` through their `crate_`,
+    /// including test dependencies and common modules
+    fn is_depended_on(&self, name: &str) -> bool {
+        self.graph.values().any(|entry| {
+            entry
+                .edges
+                .iter()
+                .chain(&entry.dev_edges)
+                .any(|x| x.name == name)
+                || entry.common_deps.iter().any(|x| x == name)
+        })
+    }
+
+    /// Find a dependency path from `from` to `to`, including both ends.
+    ///
+    /// Must only be called when there are no circular dependencies
+    fn find_path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        self.find_path_recur(from, to, false, &mut BTreeSet::new())
+    }
+
+    /// Same as [`Self::find_path`], but also through the `#[allow_cycle]` edges,
+    /// so it can be called when the graph has cycles
+    fn find_path_with_allowed(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        self.find_path_recur(from, to, true, &mut BTreeSet::new())
+    }
+
+    /// `visited` are the modules that are already known to not reach `to`,
+    /// so each module is only searched once
+    fn find_path_recur<'a>(
+        &'a self,
+        from: &'a str,
+        to: &str,
+        with_allowed: bool,
+        visited: &mut BTreeSet<&'a str>,
+    ) -> Option<Vec<String>> {
+        if from == to {
+            return Some(vec![to.to_owned()]);
+        }
+        if !visited.insert(from) {
+            return None;
+        }
+        let entry = self.graph.get(from)?;
+        let edges = entry
+            .edges
+            .iter()
+            .filter(|x| with_allowed || x.allow_cycle.is_none());
+        for edge in edges {
+            if let Some(mut path) = self.find_path_recur(&edge.name, to, with_allowed, visited) {
+                path.insert(0, from.to_owned());
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// If the edge from `entry` is implied by another dependency of `entry`,
+    /// get the path through the other dependency, not including `entry`
+    fn implied_path(&self, entry: &ModuleDecl, edge: &DepEdge) -> Option<Vec<String>> {
+        entry
+            .layered_edges()
+            .filter(|other| other.name != edge.name)
+            .find_map(|other| self.find_path(&other.name, &edge.name))
+    }
+
+    /// Path of the module containing the `#[layers]` module, from `#[layers(at = ...)]`
+    fn at(&self) -> syn::Path {
+        self.at
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote! { crate })
+    }
+
+    /// The `at` path without `crate`, like `engine::core`, or empty for the crate root
+    fn parent_module(&self) -> String {
+        self.at()
+            .segments
+            .iter()
+            .skip(1)
+            .map(|x| x.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
     fn generate_impl(&self, src_mod: &syn::Ident) -> TokenStream2 {
         let mut mod_tokens = TokenStream2::new();
+        let at = self.at();
+        let parent_module = self.parent_module();
         for entry in self.graph.values() {
-            mod_tokens.extend(entry.generate_mod_impl(src_mod, self.has_circular_deps));
+            let graph_doc = self.generate_graph_doc(entry, "");
+            mod_tokens.extend(entry.generate_mod_impl(
+                src_mod,
+                (&at, &parent_module),
+                graph_doc,
+                self.is_depended_on(&entry.ident.to_string()),
+                self.has_circular_deps,
+            ));
+        }
+        if self.doc_overview {
+            mod_tokens.extend(self.generate_overview(src_mod));
         }
         mod_tokens
     }
+
+    /// Generate the module declaration inside the `#[layers]` module
+    fn generate_src_mod(&self, name: &str) -> TokenStream2 {
+        let Some(entry) = self.graph.get(name) else {
+            return TokenStream2::new();
+        };
+        let attrs = &entry.src_attrs;
+        let decl = &entry.src_decl;
+        if entry.has_wrapper() {
+            return quote! { #attrs #decl };
+        }
+        // modules without dependencies are re-exported directly, so
+        // the graph docs need to be on the original module.
+        // Links are resolved from the `#[layers]` module, so they need `super::`
+        let graph_doc = self.generate_graph_doc(entry, "super::");
+        quote! { #attrs #graph_doc #decl }
+    }
+
+    /// Generate the doc lines about the module's place in the graph,
+    /// which are appended to the module's own docs
+    fn generate_graph_doc(&self, entry: &ModuleDecl, link_prefix: &str) -> TokenStream2 {
+        let mut paragraphs = Vec::new();
+        let link = |name: &str| match self.graph.get(name) {
+            // don't link to private modules from public docs
+            Some(x) if x.is_pub || !entry.is_pub => {
+                if link_prefix.is_empty() {
+                    format!("[`{}`]", name)
+                } else {
+                    format!("[`{}`]({}{})", name, link_prefix, name)
+                }
+            }
+            _ => format!("`{}`", name),
+        };
+        if entry.is_common {
+            paragraphs.push(" This is a common module, available to every module.".to_owned());
+        }
+        if let Some(visible_to) = &entry.visible_to {
+            paragraphs.push(format!(
+                " This module is only visible to: {}",
+                format_ident_list(visible_to)
+            ));
+        }
+        // mark the edges that are deprecated, or pointing to a deprecated module
+        let dep_link = |edge: &DepEdge| {
+            let is_deprecated = edge.deprecated.is_some()
+                || self
+                    .graph
+                    .get(&edge.name)
+                    .is_some_and(|x| x.deprecated.is_some());
+            if is_deprecated {
+                format!("{} (deprecated)", link(&edge.name))
+            } else {
+                link(&edge.name)
+            }
+        };
+        if !entry.edges.is_empty() {
+            let deps = entry.edges.iter().map(dep_link).collect::<Vec<_>>();
+            paragraphs.push(format!(" **Depends on:** {}", deps.join(", ")));
+        }
+        for edge in &entry.edges {
+            if let Some(allow_cycle) = &edge.allow_cycle {
+                paragraphs.push(format!(
+                    " **Allowed cycle** with {}: {}",
+                    link(&edge.name),
+                    allow_cycle.reason.value().trim()
+                ));
+            }
+        }
+        if !entry.dev_edges.is_empty() {
+            let deps = entry.dev_edges.iter().map(dep_link).collect::<Vec<_>>();
+            paragraphs.push(format!(" **Depends on (tests only):** {}", deps.join(", ")));
+        }
+        let dependents = self.dependents(&entry.ident.to_string());
+        if !dependents.is_empty() {
+            let dependents = dependents.into_iter().map(link).collect::<Vec<_>>();
+            paragraphs.push(format!(" **Used by:** {}", dependents.join(", ")));
+        }
+        let mut lines = Vec::with_capacity(paragraphs.len() * 2);
+        for paragraph in paragraphs {
+            // separate from the previous paragraph or the module's own docs
+            if !lines.is_empty() || !entry.docs.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(paragraph);
+        }
+        quote! { #(#[doc = #lines])* }
+    }
+
+    /// Generate the `__layers_<module>` module, which has a table of all modules in the docs.
+    ///
+    /// The name includes the `#[layers]` module, so several overviews can be in the same module
+    fn generate_overview(&self, src_mod: &syn::Ident) -> TokenStream2 {
+        let mut entries = self.graph.values().collect::<Vec<_>>();
+        entries.sort_by_key(|x| x.order);
+        let chain_lengths = if self.has_circular_deps {
+            BTreeMap::new()
+        } else {
+            self.chain_lengths()
+        };
+
+        let mut lines = vec![
+            " Overview of the layers in this crate, generated by `#[layers(doc_overview)]`."
+                .to_owned(),
+            String::new(),
+            " Modules are listed top-down. Level is the length of the longest".to_owned(),
+            " dependency chain below the module, so modules on level 0 have no dependencies."
+                .to_owned(),
+            String::new(),
+            " | Layer | Description | Visibility | Level | Depends on | Used by |".to_owned(),
+            " |-------|-------------|------------|-------|------------|---------|".to_owned(),
+        ];
+        for entry in entries {
+            let name = entry.ident.to_string();
+            let level = match chain_lengths.get(name.as_str()) {
+                Some(length) => (length - 1).to_string(),
+                None => "-".to_owned(),
+            };
+            let deps = entry
+                .edges
+                .iter()
+                .map(|x| self.overview_link(&x.name))
+                .collect::<Vec<_>>();
+            let dependents = self
+                .dependents(&name)
+                .into_iter()
+                .map(|x| self.overview_link(x))
+                .collect::<Vec<_>>();
+            lines.push(format!(
+                " | {} | {} | `{}` | {} | {} | {} |",
+                self.overview_link(&name),
+                entry.first_doc_line().replace('|', "\\|"),
+                if entry.is_pub { "pub" } else { "pub(crate)" },
+                level,
+                deps.join(", "),
+                dependents.join(", "),
+            ));
+        }
+
+        let overview_ident = quote::format_ident!("__layers_{}", src_mod);
+        quote! {
+            #(#[doc = #lines])*
+            pub mod #overview_ident {}
+        }
+    }
+
+    /// Link to a module from the overview docs. Only public modules are linked,
+    /// since the overview itself is public
+    fn overview_link(&self, name: &str) -> String {
+        match self.graph.get(name) {
+            Some(x) if x.is_pub => format!("[`{}`]", name),
+            _ => format!("`{}`", name),
+        }
+    }
 }
 
 fn format_stack(stack: &[String], next: &str) -> String {
     format!("{} -> {}", stack.join(" -> "), next)
 }
 
+fn format_ident_list(idents: &[syn::Ident]) -> String {
+    idents
+        .iter()
+        .map(|x| format!("`{}`", x))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 impl ModuleDecl {
-    fn generate_mod_impl(&self, src_mod: &syn::Ident, has_circular_deps: bool) -> TokenStream2 {
+    /// If a wrapper module with `crate_` is generated for this module,
+    /// instead of re-exporting the module directly
+    fn has_wrapper(&self) -> bool {
+        !self.edges.is_empty() || !self.dev_edges.is_empty() || !self.common_deps.is_empty()
+    }
+
+    /// Get the lines of the doc comments
+    fn doc_lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.docs.iter().filter_map(|attr| {
+            let nv = attr.meta.require_name_value().ok()?;
+            let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) = &nv.value
+            else {
+                return None;
+            };
+            Some(lit.value())
+        })
+    }
+
+    /// Get the first line of the doc comments, or empty string if no docs
+    fn first_doc_line(&self) -> String {
+        self.doc_lines()
+            .map(|line| line.trim().to_owned())
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+    }
+
+    fn generate_mod_impl(
+        &self,
+        src_mod: &syn::Ident,
+        location: (&syn::Path, &str),
+        graph_doc: TokenStream2,
+        is_depended_on: bool,
+        has_circular_deps: bool,
+    ) -> TokenStream2 {
         let vis = if self.is_pub {
             quote! { pub }
         } else {
             quote! { pub(crate) }
         };
         let doc = &self.docs;
+        let doc = quote! { #(#doc)* };
+        let cfgs = &self.cfgs;
+        let cfgs = quote! { #(#cfgs)* };
         let deps_ident = &self.ident;
 
-        if self.edges.is_empty() {
+        // the module's own re-export should not warn, but the wrapper
+        // is deprecated too, so uses through the wrapper are warned
+        let (deprecated, allow_deprecated) = match &self.deprecated {
+            Some(attr) => (quote! { #attr }, quote! { #[allow(deprecated)] }),
+            None => (quote! {}, quote! {}),
+        };
+
+        if !self.has_wrapper() {
             return quote_spanned! {
-                self.ident.span() => #[doc(inline)] #vis use #src_mod::#deps_ident;
+                self.ident.span() => #cfgs #[doc(inline)] #allow_deprecated #vis use #src_mod::#deps_ident;
             };
         }
 
+        let mut suppress_glob_lints = TokenStream2::new();
+        if self.edges.is_empty() {
+            // the wrapper only exists for the test or common dependencies,
+            // so it should not warn more than a direct re-export would
+            suppress_glob_lints.extend(quote! {
+                #[allow(unused_imports)]
+            });
+        } else if is_depended_on {
+            // dependents use the module through `crate_`, not the wrapper, so in a binary
+            // (where `pub use` can't be exported) the glob would be reported as unused
+            suppress_glob_lints.extend(quote! {
+                #[allow(unused_imports)]
+            });
+        } else {
+            // the top modules of a test harness are only used by the harness
+            suppress_glob_lints.extend(quote! {
+                #[cfg_attr(test, allow(unused_imports))]
+            });
+        }
+
         let mut suppress_lints = TokenStream2::new();
         if has_circular_deps {
             // allow unused imports in circular deps, because
@@ -389,26 +1789,252 @@ impl ModuleDecl {
 
         let mut dep_tokens = TokenStream2::new();
         for edge in &self.edges {
-            let dep_ident = &edge.ident;
+            dep_tokens.extend(edge.generate_reexport(src_mod, &quote! {}));
+        }
+        for edge in &self.dev_edges {
+            let cfg_test = quote_spanned! { edge.ident.span() => #[cfg(test)] };
+            dep_tokens.extend(edge.generate_reexport(src_mod, &cfg_test));
+        }
+        for common_ident in &self.common_deps {
+            // most modules won't use all the common modules
             dep_tokens.extend(quote_spanned! {
-                dep_ident.span() =>
-                    pub use crate::#src_mod::#dep_ident;
+                common_ident.span() =>
+                    #[allow(unused_imports)]
+                    pub use super::super::#src_mod::#common_ident;
             });
         }
+        for (common_ident, mac) in &self.common_macros {
+            // same as the macros of the edges, see `DepEdge::generate_reexport`
+            dep_tokens.extend(quote_spanned! {
+                mac.span() =>
+                    #[allow(unused_imports)]
+                    pub(crate) use super::super::#src_mod::#common_ident::#mac;
+            });
+        }
+
+        let use_deps = self.generate_use_deps(src_mod, location);
 
         quote_spanned! {
             self.ident.span() =>
+                #cfgs
                 #doc
+                #graph_doc
+                #deprecated
                 #vis mod #deps_ident {
                     #[doc(inline)]
-                    pub use crate::#src_mod::#deps_ident::*;
+                    #suppress_glob_lints
+                    #allow_deprecated
+                    pub use super::#src_mod::#deps_ident::*;
                     #[doc(hidden)]
                     #suppress_lints
                     pub(crate) mod crate_ {
                         #dep_tokens
                     }
+                    #use_deps
+                }
+        }
+    }
+
+    /// Generate the `use_deps!()` macro, which imports all dependencies
+    /// from `crate_` when used inside the module.
+    ///
+    /// The macro can be used in any submodule of the layer, so it needs absolute paths,
+    /// built from the `at` path and its module path string (without `crate`)
+    fn generate_use_deps(
+        &self,
+        src_mod: &syn::Ident,
+        (at, parent_module): (&syn::Path, &str),
+    ) -> TokenStream2 {
+        let deps_ident = &self.ident;
+        let module = if parent_module.is_empty() {
+            format!("{}::{}", src_mod, deps_ident)
+        } else {
+            format!("{}::{}::{}", parent_module, src_mod, deps_ident)
+        };
+        let message = format!(
+            "[LC0016] {}::use_deps!() can only be used inside the `{}` module",
+            deps_ident, deps_ident
+        );
+        let location_message = format!(
+            "[LC0023] the `{}` module is not in `{}`, add `at = path::to::parent` to `#[layers]` \
+             with the path of the module that contains it",
+            src_mod,
+            if parent_module.is_empty() {
+                "crate".to_string()
+            } else {
+                format!("crate::{}", parent_module)
+            }
+        );
+        let module_path_check = generate_module_path_check(
+            &module,
+            &format!("{}::{}", src_mod, deps_ident),
+            &message,
+            &location_message,
+        );
+        // deprecated edges are imported by name, since the glob doesn't warn
+        let mut deprecated_imports = TokenStream2::new();
+        for edge in &self.edges {
+            if edge.deprecated.is_some() {
+                let dep_ident = &edge.ident;
+                deprecated_imports.extend(quote! {
+                    #[allow(unused_imports)]
+                    use crate_::#dep_ident;
+                });
+            }
+        }
+        for edge in &self.dev_edges {
+            if edge.deprecated.is_some() {
+                let dep_ident = &edge.ident;
+                deprecated_imports.extend(quote! {
+                    #[cfg(test)]
+                    #[allow(unused_imports)]
+                    use crate_::#dep_ident;
+                });
+            }
+        }
+        quote! {
+            #[doc(hidden)]
+            #[allow(unused_macros)]
+            macro_rules! use_deps {
+                () => {
+                    #module_path_check
+                    // the wrapper of a deprecated module is deprecated too,
+                    // including `crate_`, which should not warn in the module itself
+                    #[allow(unused_imports, deprecated)]
+                    use #at::#deps_ident::crate_;
+                    // a glob, so unused dependencies are reported at the re-exports
+                    // in `crate_` (at the attribute), and a wrong module doesn't
+                    // get unused import warnings on top of the error
+                    #[allow(unused_imports, deprecated)]
+                    use crate_::*;
+                    #deprecated_imports
+                };
+            }
+            #[doc(hidden)]
+            #[allow(unused_imports)]
+            pub(crate) use use_deps;
+        }
+    }
+}
+
+/// Generate the check in `use_deps!()` that it's used in the right module.
+///
+/// `module` is the full path of the layer (without the crate name), and `layer_module`
+/// is the path from the `#[layers]` module. When `module_path!()` has `layer_module`
+/// somewhere else, the macro is used in the right layer, but `at` is wrong.
+///
+/// This can't call a function in the `#[layers]` module, since the path
+/// to it is from `at`, so everything is inlined
+fn generate_module_path_check(
+    module: &str,
+    layer_module: &str,
+    message: &str,
+    location_message: &str,
+) -> TokenStream2 {
+    quote! {
+        const _: () = {
+            /// Check if the segments of `path` from `start` are `module`,
+            /// followed by the end or more segments
+            const fn is_at(path: &[u8], start: usize, module: &[u8]) -> bool {
+                if path.len() < start + module.len() {
+                    return false;
+                }
+                let mut i = 0;
+                while i < module.len() {
+                    if path[start + i] != module[i] {
+                        return false;
+                    }
+                    i += 1;
                 }
+                path.len() == start + module.len() || path[start + module.len()] == b':'
+            }
+            let path = module_path!().as_bytes();
+            // skip the crate name
+            let mut start = 0;
+            while start + 1 < path.len() && !(path[start] == b':' && path[start + 1] == b':') {
+                start += 1;
+            }
+            start += 2;
+            let in_module = is_at(path, start, #module.as_bytes());
+            let mut in_layer = false;
+            let mut i = start;
+            while i < path.len() {
+                if (i == start || path[i - 1] == b':') && is_at(path, i, #layer_module.as_bytes()) {
+                    in_layer = true;
+                }
+                i += 1;
+            }
+            assert!(in_module || !in_layer, #location_message);
+            assert!(in_module, #message);
+        };
+    }
+}
+
+impl DepEdge {
+    /// Generate the re-export of the dependency in `crate_`, with `attrs` on each item
+    fn generate_reexport(&self, src_mod: &syn::Ident, attrs: &TokenStream2) -> TokenStream2 {
+        let dep_ident = &self.ident;
+        // `macro_rules!` macros can at most be `pub(crate)`, so they can't be in the `pub use`.
+        // They are also re-exported directly in `crate_` for `use_deps!()`,
+        // where most modules won't use all of them.
+        // Spanned at the macro, so a macro that's not exported is an error at `#[exports_macros]`
+        let mut tokens = TokenStream2::new();
+        let mut facade_macros = TokenStream2::new();
+        for mac in &self.macros {
+            tokens.extend(quote_spanned! {
+                mac.span() =>
+                    #attrs
+                    #[allow(unused_imports)]
+                    pub(crate) use super::super::#src_mod::#dep_ident::#mac;
+            });
+            facade_macros.extend(quote_spanned! {
+                mac.span() =>
+                    #[allow(unused_imports)]
+                    pub(crate) use super::super::super::#src_mod::#dep_ident::#mac;
+            });
+        }
+        if let Some(note) = &self.deprecated {
+            // re-export through a deprecated module, so imports of it are warned.
+            // The `use ... as _` warns at the attribute, and `use_deps!()` imports it
+            // by name to warn at the call (uses through a glob import are not warned)
+            let items = match &self.items {
+                None => quote! { * },
+                Some(items) => quote! { {#(#items),*} },
+            };
+            tokens.extend(quote_spanned! {
+                dep_ident.span() =>
+                    #attrs
+                    #[deprecated(note = #note)]
+                    pub mod #dep_ident {
+                        pub use super::super::super::#src_mod::#dep_ident::#items;
+                        #facade_macros
+                    }
+                    #attrs
+                    #[allow(unused_imports)]
+                    use self::#dep_ident as _;
+            });
+            return tokens;
         }
+        tokens.extend(match &self.items {
+            None => quote_spanned! {
+                dep_ident.span() =>
+                    #attrs
+                    pub use super::super::#src_mod::#dep_ident;
+            },
+            Some(items) => {
+                // only re-export the listed items, so unknown items
+                // are errors at the attribute
+                quote_spanned! {
+                    dep_ident.span() =>
+                        #attrs
+                        pub mod #dep_ident {
+                            pub use super::super::super::#src_mod::#dep_ident::{#(#items),*};
+                            #facade_macros
+                        }
+                }
+            }
+        });
+        tokens
     }
 }
 
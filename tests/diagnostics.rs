@@ -0,0 +1,35 @@
+use std::collections::BTreeSet;
+
+/// Every diagnostic code used in the source should be explained in DIAGNOSTICS.md,
+/// and every code explained there should be used in the source
+#[test]
+fn diagnostic_codes_are_documented() {
+    let docs = std::fs::read_to_string("DIAGNOSTICS.md").unwrap();
+    let documented = docs
+        .lines()
+        .filter_map(|line| line.strip_prefix("## "))
+        .filter(|x| x.starts_with("LC"))
+        .collect::<BTreeSet<_>>();
+
+    let mut used = BTreeSet::new();
+    for entry in std::fs::read_dir("src").unwrap() {
+        let source = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+        for (i, _) in source.match_indices("[LC") {
+            used.insert(source[i + 1..i + 7].to_string());
+        }
+    }
+
+    assert!(!used.is_empty());
+    for code in &used {
+        assert!(
+            documented.contains(code.as_str()),
+            "{code} is not in DIAGNOSTICS.md"
+        );
+    }
+    for code in &documented {
+        assert!(
+            used.contains(*code),
+            "{code} is documented but never emitted"
+        );
+    }
+}
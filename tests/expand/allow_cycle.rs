@@ -0,0 +1,14 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    /// Public APIs
+    #[depends_on(engine)]
+    #[allow_cycle(engine, reason = "`engine` is still being split from here")]
+    pub extern crate api;
+
+    /// The engine, being split from api
+    #[depends_on(api)]
+    #[allow_cycle(api, reason = "`api::Config` is still used here, until it moves to `engine`")]
+    pub extern crate engine;
+}
@@ -0,0 +1,132 @@
+use layered_crate::layers;
+#[doc(hidden)]
+pub(crate) mod src {
+    pub mod api {}
+    pub mod sub_system {}
+    pub mod errors {}
+    /// This is a common module, available to every module.
+    ///
+    /// **Used by:** [`errors`](super::errors)
+    pub mod utils {}
+}
+/// **Depends on:** [`sub_system`]
+pub mod api {
+    #[doc(inline)]
+    pub use super::src::api::*;
+    #[doc(hidden)]
+    pub(crate) mod crate_ {
+        pub use super::super::src::sub_system;
+        #[allow(unused_imports)]
+        pub use super::super::src::errors;
+        #[allow(unused_imports)]
+        pub use super::super::src::utils;
+    }
+    #[doc(hidden)]
+    #[allow(unused_macros)]
+    macro_rules! use_deps {
+        () => {
+            const _ : () = { #[doc =
+            r" Check if the segments of `path` from `start` are `module`,"] #[doc =
+            r" followed by the end or more segments"] const fn is_at(path : & [u8], start
+            : usize, module : & [u8]) -> bool { if path.len() < start + module.len() {
+            return false; } let mut i = 0; while i < module.len() { if path[start + i] !=
+            module[i] { return false; } i += 1; } path.len() == start + module.len() ||
+            path[start + module.len()] == b':' } let path = module_path!() .as_bytes();
+            let mut start = 0; while start + 1 < path.len() && ! (path[start] == b':' &&
+            path[start + 1] == b':') { start += 1; } start += 2; let in_module =
+            is_at(path, start, "src::api".as_bytes()); let mut in_layer = false; let mut
+            i = start; while i < path.len() { if (i == start || path[i - 1] == b':') &&
+            is_at(path, i, "src::api".as_bytes()) { in_layer = true; } i += 1; }
+            assert!(in_module || ! in_layer,
+            "[LC0023] the `src` module is not in `crate`, add `at = path::to::parent` to `#[layers]` with the path of the module that contains it");
+            assert!(in_module,
+            "[LC0016] api::use_deps!() can only be used inside the `api` module"); };
+            #[allow(unused_imports, deprecated)] use crate ::api::crate_;
+            #[allow(unused_imports, deprecated)] use crate_:: *;
+        };
+    }
+    #[doc(hidden)]
+    #[allow(unused_imports)]
+    pub(crate) use use_deps;
+}
+/// This is a common module, available to every module.
+///
+/// **Depends on:** [`utils`]
+pub(crate) mod errors {
+    #[doc(inline)]
+    #[allow(unused_imports)]
+    pub use super::src::errors::*;
+    #[doc(hidden)]
+    pub(crate) mod crate_ {
+        pub use super::super::src::utils;
+    }
+    #[doc(hidden)]
+    #[allow(unused_macros)]
+    macro_rules! use_deps {
+        () => {
+            const _ : () = { #[doc =
+            r" Check if the segments of `path` from `start` are `module`,"] #[doc =
+            r" followed by the end or more segments"] const fn is_at(path : & [u8], start
+            : usize, module : & [u8]) -> bool { if path.len() < start + module.len() {
+            return false; } let mut i = 0; while i < module.len() { if path[start + i] !=
+            module[i] { return false; } i += 1; } path.len() == start + module.len() ||
+            path[start + module.len()] == b':' } let path = module_path!() .as_bytes();
+            let mut start = 0; while start + 1 < path.len() && ! (path[start] == b':' &&
+            path[start + 1] == b':') { start += 1; } start += 2; let in_module =
+            is_at(path, start, "src::errors".as_bytes()); let mut in_layer = false; let
+            mut i = start; while i < path.len() { if (i == start || path[i - 1] == b':')
+            && is_at(path, i, "src::errors".as_bytes()) { in_layer = true; } i += 1; }
+            assert!(in_module || ! in_layer,
+            "[LC0023] the `src` module is not in `crate`, add `at = path::to::parent` to `#[layers]` with the path of the module that contains it");
+            assert!(in_module,
+            "[LC0016] errors::use_deps!() can only be used inside the `errors` module");
+            }; #[allow(unused_imports, deprecated)] use crate ::errors::crate_;
+            #[allow(unused_imports, deprecated)] use crate_:: *;
+        };
+    }
+    #[doc(hidden)]
+    #[allow(unused_imports)]
+    pub(crate) use use_deps;
+}
+/// **Used by:** [`api`]
+pub mod sub_system {
+    #[doc(inline)]
+    #[allow(unused_imports)]
+    pub use super::src::sub_system::*;
+    #[doc(hidden)]
+    pub(crate) mod crate_ {
+        #[allow(unused_imports)]
+        pub use super::super::src::errors;
+        #[allow(unused_imports)]
+        pub use super::super::src::utils;
+    }
+    #[doc(hidden)]
+    #[allow(unused_macros)]
+    macro_rules! use_deps {
+        () => {
+            const _ : () = { #[doc =
+            r" Check if the segments of `path` from `start` are `module`,"] #[doc =
+            r" followed by the end or more segments"] const fn is_at(path : & [u8], start
+            : usize, module : & [u8]) -> bool { if path.len() < start + module.len() {
+            return false; } let mut i = 0; while i < module.len() { if path[start + i] !=
+            module[i] { return false; } i += 1; } path.len() == start + module.len() ||
+            path[start + module.len()] == b':' } let path = module_path!() .as_bytes();
+            let mut start = 0; while start + 1 < path.len() && ! (path[start] == b':' &&
+            path[start + 1] == b':') { start += 1; } start += 2; let in_module =
+            is_at(path, start, "src::sub_system".as_bytes()); let mut in_layer = false;
+            let mut i = start; while i < path.len() { if (i == start || path[i - 1] ==
+            b':') && is_at(path, i, "src::sub_system".as_bytes()) { in_layer = true; } i
+            += 1; } assert!(in_module || ! in_layer,
+            "[LC0023] the `src` module is not in `crate`, add `at = path::to::parent` to `#[layers]` with the path of the module that contains it");
+            assert!(in_module,
+            "[LC0016] sub_system::use_deps!() can only be used inside the `sub_system` module");
+            }; #[allow(unused_imports, deprecated)] use crate ::sub_system::crate_;
+            #[allow(unused_imports, deprecated)] use crate_:: *;
+        };
+    }
+    #[doc(hidden)]
+    #[allow(unused_imports)]
+    pub(crate) use use_deps;
+}
+#[doc(inline)]
+pub(crate) use src::utils;
@@ -0,0 +1,15 @@
+use layered_crate::layers;
+
+#[layers(common = [utils])]
+mod src {
+    #[depends_on(sub_system)]
+    pub extern crate api;
+
+    pub extern crate sub_system;
+
+    #[depends_on(utils)]
+    #[common]
+    extern crate errors;
+
+    extern crate utils;
+}
@@ -0,0 +1,14 @@
+layered_crate::define_layers! {
+    #[layers(check_redundant_deps)]
+    mod src {
+        /// My Public APIs
+        api -> [sub_system_1];
+
+        /// Sub-system 1 if you need
+        #[visible_to(api)]
+        pub sub_system_1 -> [utils::{helper}];
+
+        /// Internal utils
+        utils;
+    }
+}
@@ -0,0 +1,98 @@
+use layered_crate::layers;
+#[doc(hidden)]
+pub(crate) mod src {
+    pub mod api {}
+    /// Old storage
+    #[deprecated(note = "use `storage_v2` instead")]
+    pub mod storage {}
+    /// **Used by:** [`api`](super::api), [`storage`](super::storage)
+    pub mod legacy {}
+}
+/// **Depends on:** [`storage`] (deprecated), [`legacy`] (deprecated)
+pub mod api {
+    #[doc(inline)]
+    pub use super::src::api::*;
+    #[doc(hidden)]
+    pub(crate) mod crate_ {
+        pub use super::super::src::storage;
+        #[deprecated(note = "inline what is still needed from `legacy`")]
+        pub mod legacy {
+            pub use super::super::super::src::legacy::*;
+        }
+        #[allow(unused_imports)]
+        use self::legacy as _;
+    }
+    #[doc(hidden)]
+    #[allow(unused_macros)]
+    macro_rules! use_deps {
+        () => {
+            const _ : () = { #[doc =
+            r" Check if the segments of `path` from `start` are `module`,"] #[doc =
+            r" followed by the end or more segments"] const fn is_at(path : & [u8], start
+            : usize, module : & [u8]) -> bool { if path.len() < start + module.len() {
+            return false; } let mut i = 0; while i < module.len() { if path[start + i] !=
+            module[i] { return false; } i += 1; } path.len() == start + module.len() ||
+            path[start + module.len()] == b':' } let path = module_path!() .as_bytes();
+            let mut start = 0; while start + 1 < path.len() && ! (path[start] == b':' &&
+            path[start + 1] == b':') { start += 1; } start += 2; let in_module =
+            is_at(path, start, "src::api".as_bytes()); let mut in_layer = false; let mut
+            i = start; while i < path.len() { if (i == start || path[i - 1] == b':') &&
+            is_at(path, i, "src::api".as_bytes()) { in_layer = true; } i += 1; }
+            assert!(in_module || ! in_layer,
+            "[LC0023] the `src` module is not in `crate`, add `at = path::to::parent` to `#[layers]` with the path of the module that contains it");
+            assert!(in_module,
+            "[LC0016] api::use_deps!() can only be used inside the `api` module"); };
+            #[allow(unused_imports, deprecated)] use crate ::api::crate_;
+            #[allow(unused_imports, deprecated)] use crate_:: *; #[allow(unused_imports)]
+            use crate_::legacy;
+        };
+    }
+    #[doc(hidden)]
+    #[allow(unused_imports)]
+    pub(crate) use use_deps;
+}
+#[doc(inline)]
+pub use src::legacy;
+/// Old storage
+///
+/// **Depends on:** [`legacy`]
+///
+/// **Used by:** [`api`]
+#[deprecated(note = "use `storage_v2` instead")]
+pub mod storage {
+    #[doc(inline)]
+    #[allow(unused_imports)]
+    #[allow(deprecated)]
+    pub use super::src::storage::*;
+    #[doc(hidden)]
+    pub(crate) mod crate_ {
+        pub use super::super::src::legacy;
+    }
+    #[doc(hidden)]
+    #[allow(unused_macros)]
+    macro_rules! use_deps {
+        () => {
+            const _ : () = { #[doc =
+            r" Check if the segments of `path` from `start` are `module`,"] #[doc =
+            r" followed by the end or more segments"] const fn is_at(path : & [u8], start
+            : usize, module : & [u8]) -> bool { if path.len() < start + module.len() {
+            return false; } let mut i = 0; while i < module.len() { if path[start + i] !=
+            module[i] { return false; } i += 1; } path.len() == start + module.len() ||
+            path[start + module.len()] == b':' } let path = module_path!() .as_bytes();
+            let mut start = 0; while start + 1 < path.len() && ! (path[start] == b':' &&
+            path[start + 1] == b':') { start += 1; } start += 2; let in_module =
+            is_at(path, start, "src::storage".as_bytes()); let mut in_layer = false; let
+            mut i = start; while i < path.len() { if (i == start || path[i - 1] == b':')
+            && is_at(path, i, "src::storage".as_bytes()) { in_layer = true; } i += 1; }
+            assert!(in_module || ! in_layer,
+            "[LC0023] the `src` module is not in `crate`, add `at = path::to::parent` to `#[layers]` with the path of the module that contains it");
+            assert!(in_module,
+            "[LC0016] storage::use_deps!() can only be used inside the `storage` module");
+            }; #[allow(unused_imports, deprecated)] use crate ::storage::crate_;
+            #[allow(unused_imports, deprecated)] use crate_:: *;
+        };
+    }
+    #[doc(hidden)]
+    #[allow(unused_imports)]
+    pub(crate) use use_deps;
+}
@@ -0,0 +1,15 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(storage)]
+    #[depends_on(legacy, deprecated = "inline what is still needed from `legacy`")]
+    pub extern crate api;
+
+    /// Old storage
+    #[deprecated(note = "use `storage_v2` instead")]
+    #[depends_on(legacy)]
+    pub extern crate storage;
+
+    pub extern crate legacy;
+}
@@ -0,0 +1,117 @@
+use layered_crate::layers;
+#[doc(hidden)]
+pub(crate) mod src {
+    pub mod api {}
+    pub mod test_support {}
+    pub mod utils {}
+}
+/// **Depends on:** `utils`
+pub mod api {
+    #[doc(inline)]
+    pub use super::src::api::*;
+    #[doc(hidden)]
+    pub(crate) mod crate_ {
+        pub use super::super::src::utils;
+    }
+    #[doc(hidden)]
+    #[allow(unused_macros)]
+    macro_rules! use_deps {
+        () => {
+            const _ : () = { #[doc =
+            r" Check if the segments of `path` from `start` are `module`,"] #[doc =
+            r" followed by the end or more segments"] const fn is_at(path : & [u8], start
+            : usize, module : & [u8]) -> bool { if path.len() < start + module.len() {
+            return false; } let mut i = 0; while i < module.len() { if path[start + i] !=
+            module[i] { return false; } i += 1; } path.len() == start + module.len() ||
+            path[start + module.len()] == b':' } let path = module_path!() .as_bytes();
+            let mut start = 0; while start + 1 < path.len() && ! (path[start] == b':' &&
+            path[start + 1] == b':') { start += 1; } start += 2; let in_module =
+            is_at(path, start, "src::api".as_bytes()); let mut in_layer = false; let mut
+            i = start; while i < path.len() { if (i == start || path[i - 1] == b':') &&
+            is_at(path, i, "src::api".as_bytes()) { in_layer = true; } i += 1; }
+            assert!(in_module || ! in_layer,
+            "[LC0023] the `src` module is not in `crate`, add `at = path::to::parent` to `#[layers]` with the path of the module that contains it");
+            assert!(in_module,
+            "[LC0016] api::use_deps!() can only be used inside the `api` module"); };
+            #[allow(unused_imports, deprecated)] use crate ::api::crate_;
+            #[allow(unused_imports, deprecated)] use crate_:: *;
+        };
+    }
+    #[doc(hidden)]
+    #[allow(unused_imports)]
+    pub(crate) use use_deps;
+}
+/// **Depends on:** [`utils`]
+pub(crate) mod test_support {
+    #[doc(inline)]
+    #[allow(unused_imports)]
+    pub use super::src::test_support::*;
+    #[doc(hidden)]
+    pub(crate) mod crate_ {
+        pub use super::super::src::utils;
+    }
+    #[doc(hidden)]
+    #[allow(unused_macros)]
+    macro_rules! use_deps {
+        () => {
+            const _ : () = { #[doc =
+            r" Check if the segments of `path` from `start` are `module`,"] #[doc =
+            r" followed by the end or more segments"] const fn is_at(path : & [u8], start
+            : usize, module : & [u8]) -> bool { if path.len() < start + module.len() {
+            return false; } let mut i = 0; while i < module.len() { if path[start + i] !=
+            module[i] { return false; } i += 1; } path.len() == start + module.len() ||
+            path[start + module.len()] == b':' } let path = module_path!() .as_bytes();
+            let mut start = 0; while start + 1 < path.len() && ! (path[start] == b':' &&
+            path[start + 1] == b':') { start += 1; } start += 2; let in_module =
+            is_at(path, start, "src::test_support".as_bytes()); let mut in_layer = false;
+            let mut i = start; while i < path.len() { if (i == start || path[i - 1] ==
+            b':') && is_at(path, i, "src::test_support".as_bytes()) { in_layer = true; }
+            i += 1; } assert!(in_module || ! in_layer,
+            "[LC0023] the `src` module is not in `crate`, add `at = path::to::parent` to `#[layers]` with the path of the module that contains it");
+            assert!(in_module,
+            "[LC0016] test_support::use_deps!() can only be used inside the `test_support` module");
+            }; #[allow(unused_imports, deprecated)] use crate ::test_support::crate_;
+            #[allow(unused_imports, deprecated)] use crate_:: *;
+        };
+    }
+    #[doc(hidden)]
+    #[allow(unused_imports)]
+    pub(crate) use use_deps;
+}
+/// **Depends on (tests only):** [`test_support`]
+///
+/// **Used by:** [`api`], [`test_support`]
+pub(crate) mod utils {
+    #[doc(inline)]
+    #[allow(unused_imports)]
+    pub use super::src::utils::*;
+    #[doc(hidden)]
+    pub(crate) mod crate_ {}
+    #[doc(hidden)]
+    #[allow(unused_macros)]
+    macro_rules! use_deps {
+        () => {
+            const _ : () = { #[doc =
+            r" Check if the segments of `path` from `start` are `module`,"] #[doc =
+            r" followed by the end or more segments"] const fn is_at(path : & [u8], start
+            : usize, module : & [u8]) -> bool { if path.len() < start + module.len() {
+            return false; } let mut i = 0; while i < module.len() { if path[start + i] !=
+            module[i] { return false; } i += 1; } path.len() == start + module.len() ||
+            path[start + module.len()] == b':' } let path = module_path!() .as_bytes();
+            let mut start = 0; while start + 1 < path.len() && ! (path[start] == b':' &&
+            path[start + 1] == b':') { start += 1; } start += 2; let in_module =
+            is_at(path, start, "src::utils".as_bytes()); let mut in_layer = false; let
+            mut i = start; while i < path.len() { if (i == start || path[i - 1] == b':')
+            && is_at(path, i, "src::utils".as_bytes()) { in_layer = true; } i += 1; }
+            assert!(in_module || ! in_layer,
+            "[LC0023] the `src` module is not in `crate`, add `at = path::to::parent` to `#[layers]` with the path of the module that contains it");
+            assert!(in_module,
+            "[LC0016] utils::use_deps!() can only be used inside the `utils` module"); };
+            #[allow(unused_imports, deprecated)] use crate ::utils::crate_;
+            #[allow(unused_imports, deprecated)] use crate_:: *;
+        };
+    }
+    #[doc(hidden)]
+    #[allow(unused_imports)]
+    pub(crate) use use_deps;
+}
@@ -0,0 +1,13 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(utils)]
+    pub extern crate api;
+
+    #[depends_on(utils)]
+    extern crate test_support;
+
+    #[dev_depends_on(test_support)]
+    extern crate utils;
+}
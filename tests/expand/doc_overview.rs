@@ -0,0 +1,28 @@
+use layered_crate::layers;
+
+#[layers(doc_overview)]
+mod src {
+    /// My Public APIs
+    #[depends_on(sub_system_1)]
+    #[depends_on(utils)]
+    pub extern crate api;
+
+    /// Sub-system 1 if you need
+    ///
+    /// More details
+    #[depends_on(utils)]
+    pub extern crate sub_system_1;
+
+    /// Internal utils | helpers
+    extern crate utils;
+}
+
+#[layers(doc_overview)]
+mod tools {
+    /// Command line interface
+    #[depends_on(config)]
+    pub extern crate cli;
+
+    /// Configuration files
+    extern crate config;
+}
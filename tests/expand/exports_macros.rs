@@ -0,0 +1,15 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(logging)]
+    #[depends_on(config::{Config})]
+    pub extern crate api;
+
+    #[depends_on(logging)]
+    #[dev_depends_on(config)]
+    pub extern crate config;
+
+    #[exports_macros(log_event, trace)]
+    pub extern crate logging;
+}
@@ -0,0 +1,13 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(sub_system::run)]
+    #[depends_on(utils::{parse, Config})]
+    pub extern crate api;
+
+    #[depends_on(utils)]
+    pub extern crate sub_system;
+
+    extern crate utils;
+}
@@ -5,6 +5,7 @@ use layered_crate::layers;
 pub(crate) mod src {
     pub mod x {}
     #[this_is_kept]
+    /// **Used by:** [`x`](super::x)
     pub mod y {}
     pub mod z {
         pub fn hey() {}
@@ -15,13 +16,41 @@ pub(crate) mod src {
         };
     }
 }
+/// **Depends on:** [`y`]
 pub mod x {
     #[doc(inline)]
-    pub use crate::src::x::*;
+    pub use super::src::x::*;
     #[doc(hidden)]
     pub(crate) mod crate_ {
-        pub use crate::src::y;
+        pub use super::super::src::y;
     }
+    #[doc(hidden)]
+    #[allow(unused_macros)]
+    macro_rules! use_deps {
+        () => {
+            const _ : () = { #[doc =
+            r" Check if the segments of `path` from `start` are `module`,"] #[doc =
+            r" followed by the end or more segments"] const fn is_at(path : & [u8], start
+            : usize, module : & [u8]) -> bool { if path.len() < start + module.len() {
+            return false; } let mut i = 0; while i < module.len() { if path[start + i] !=
+            module[i] { return false; } i += 1; } path.len() == start + module.len() ||
+            path[start + module.len()] == b':' } let path = module_path!() .as_bytes();
+            let mut start = 0; while start + 1 < path.len() && ! (path[start] == b':' &&
+            path[start + 1] == b':') { start += 1; } start += 2; let in_module =
+            is_at(path, start, "src::x".as_bytes()); let mut in_layer = false; let mut i
+            = start; while i < path.len() { if (i == start || path[i - 1] == b':') &&
+            is_at(path, i, "src::x".as_bytes()) { in_layer = true; } i += 1; }
+            assert!(in_module || ! in_layer,
+            "[LC0023] the `src` module is not in `crate`, add `at = path::to::parent` to `#[layers]` with the path of the module that contains it");
+            assert!(in_module,
+            "[LC0016] x::use_deps!() can only be used inside the `x` module"); };
+            #[allow(unused_imports, deprecated)] use crate ::x::crate_;
+            #[allow(unused_imports, deprecated)] use crate_:: *;
+        };
+    }
+    #[doc(hidden)]
+    #[allow(unused_imports)]
+    pub(crate) use use_deps;
 }
 #[doc(inline)]
 pub use src::y;
@@ -0,0 +1,12 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    /// Platform abstraction
+    #[depends_on(unsafe_ffi)]
+    pub extern crate platform;
+
+    /// Raw bindings
+    #[visible_to(platform)]
+    extern crate unsafe_ffi;
+}
@@ -0,0 +1,5 @@
+crate::dsl::api::use_deps!();
+
+pub fn run() -> u32 {
+    sub_system_1::value()
+}
@@ -0,0 +1,5 @@
+crate::dsl::sub_system_1::use_deps!();
+
+pub fn value() -> u32 {
+    utils::helper() + 1
+}
@@ -0,0 +1,3 @@
+pub fn shape_count() -> usize {
+    3
+}
@@ -0,0 +1,5 @@
+crate::engine::render::use_deps!();
+
+pub fn draw() -> String {
+    format!("drawing {} shapes", core::shape_count())
+}
@@ -1,6 +1,6 @@
 use layered_crate::layers;
 
-#[layers]
+#[layers(doc_overview)]
 pub mod src {
     /// My Public APIs
     #[depends_on(sub_system_1)]
@@ -16,9 +16,71 @@ pub mod src {
     #[depends_on(utils)]
     pub extern crate sub_system_2;
 
+    /// Helpers for tests
+    #[cfg(test)]
+    #[depends_on(utils)]
+    pub extern crate test_support;
+
     /// Internal utils
+    #[dev_depends_on(test_support)]
+    #[exports_macros(double)]
     extern crate utils;
 }
 
 #[doc(inline)]
 pub use api::*;
+
+#[layers(auto = "plugins", doc_overview)]
+mod plugins {}
+
+/// A layered subsystem that is not in the crate root
+pub mod engine {
+    use layered_crate::layers;
+
+    #[layers(at = crate::engine)]
+    mod src {
+        #[depends_on(core)]
+        pub extern crate render;
+
+        extern crate core;
+    }
+}
+
+/// Layers declared with `define_layers!`, same as the example in its docs
+pub mod dsl {
+    layered_crate::define_layers! {
+        #[layers(check_redundant_deps, at = crate::dsl)]
+        mod src {
+            /// My Public APIs
+            api -> [sub_system_1];
+
+            /// Sub-system 1 if you need
+            #[visible_to(api)]
+            pub sub_system_1 -> [utils::{helper}];
+
+            /// Internal utils
+            utils;
+        }
+    }
+
+    pub fn run() -> u32 {
+        api::run()
+    }
+}
+
+/// Layers with a common module that exports macros
+pub mod shared {
+    use layered_crate::layers;
+
+    #[layers(common = [macros], at = crate::shared)]
+    mod src {
+        pub extern crate app;
+
+        #[exports_macros(triple)]
+        extern crate macros;
+    }
+
+    pub fn run() -> u32 {
+        app::run()
+    }
+}
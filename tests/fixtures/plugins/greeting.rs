@@ -0,0 +1,10 @@
+// #![public]
+// #![depends_on(names)]
+
+//! Greeting plugin, discovered by `#[layers(auto = "plugins")]`
+
+crate::greeting::use_deps!();
+
+pub fn greet() -> String {
+    format!("Hello, {}!", names::default_name())
+}
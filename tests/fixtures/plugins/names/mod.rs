@@ -0,0 +1,5 @@
+//! Names for the plugins
+
+pub fn default_name() -> &'static str {
+    "world"
+}
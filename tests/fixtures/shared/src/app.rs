@@ -0,0 +1,6 @@
+crate::shared::app::use_deps!();
+
+pub fn run() -> u32 {
+    // from the common module, without a `#[depends_on]`
+    triple!(14)
+}
@@ -0,0 +1,7 @@
+/// Multiply by 3, available to every module since this module is common
+macro_rules! triple {
+    ($x:expr) => {
+        $x * 3
+    };
+}
+pub(crate) use triple;
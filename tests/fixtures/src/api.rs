@@ -1,6 +1,4 @@
-use crate::api::crate_;
-
-use crate_::{utils, sub_system_1, sub_system_2};
+crate::api::use_deps!();
 
 pub fn add(a: i32, b: i32) -> i32 {
     utils::x();
@@ -12,3 +10,7 @@ pub fn add(a: i32, b: i32) -> i32 {
 pub fn sub(a: i32, b: i32) -> i32 {
     a - b
 }
+
+pub fn twice(a: i32) -> i32 {
+    double!(a)
+}
@@ -4,5 +4,5 @@ use crate_::utils;
 
 pub fn sub2() -> u32 {
     utils::x();
-    42
+    utils::double!(21)
 }
@@ -0,0 +1,8 @@
+use crate::test_support::crate_;
+
+use crate_::utils;
+
+pub fn checked_x() -> bool {
+    utils::x();
+    true
+}
@@ -1 +1,19 @@
 pub fn x() {}
+
+/// Multiply by 2, exported to the dependents with `#[exports_macros]`
+macro_rules! double {
+    ($x:expr) => {
+        $x * 2
+    };
+}
+pub(crate) use double;
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::crate_::test_support;
+
+    #[test]
+    fn test_x() {
+        assert!(test_support::checked_x());
+    }
+}
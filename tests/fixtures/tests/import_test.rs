@@ -3,6 +3,11 @@ fn import_from_fixture_and_compile_and_run() {
     // should have these exported
     assert_eq!(fixtures::add(3, 4), 7);
     assert_eq!(fixtures::sub(2, 2), 0);
+    assert_eq!(fixtures::twice(4), 8); // macro from #[exports_macros]
     assert_eq!(fixtures::sub_system_2::sub2(), 42);
     assert_eq!(fixtures::sub_system_1::sub1(), 37); // re-exported from foo
+    assert_eq!(fixtures::greeting::greet(), "Hello, world!"); // discovered from plugins/
+    assert_eq!(fixtures::engine::render::draw(), "drawing 3 shapes"); // layers not in the crate root
+    assert_eq!(fixtures::dsl::run(), 43); // layers from define_layers!
+    assert_eq!(fixtures::shared::run(), 42); // macro from a common module
 }
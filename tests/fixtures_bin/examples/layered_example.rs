@@ -0,0 +1,25 @@
+//! Example with layers declared as inline modules
+
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(greeting)]
+    pub mod app {
+        crate::app::use_deps!();
+
+        pub fn run() -> String {
+            greeting::hello("example")
+        }
+    }
+
+    pub mod greeting {
+        pub fn hello(name: &str) -> String {
+            format!("Hello, {name}!")
+        }
+    }
+}
+
+fn main() {
+    println!("{}", app::run());
+}
@@ -0,0 +1,8 @@
+crate::cli::use_deps!();
+
+use alloc::string::String;
+
+pub fn run(args: impl Iterator<Item = String>) -> String {
+    let config = config::Config::from_args(args);
+    store::describe(&config)
+}
@@ -0,0 +1,10 @@
+pub struct Config {
+    pub path: String,
+}
+
+impl Config {
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        let path = args.next().unwrap_or_else(|| "data".to_string());
+        Self { path }
+    }
+}
@@ -0,0 +1,5 @@
+crate::store::use_deps!();
+
+pub fn describe(config: &config::Config) -> String {
+    format!("storing to {}", config.path)
+}
@@ -0,0 +1,27 @@
+//! Binary crate with layers, to test that the generated code
+//! works without a library target
+
+// real extern crates can be mixed with the placeholders
+extern crate alloc;
+
+use layered_crate::layers;
+
+#[layers]
+mod layers {
+    /// Command line interface
+    #[depends_on(store)]
+    #[depends_on(config)]
+    pub extern crate cli;
+
+    /// Storage
+    #[depends_on(config)]
+    extern crate store;
+
+    /// Configuration
+    extern crate config;
+}
+
+fn main() {
+    let output = cli::run(std::env::args().skip(1));
+    println!("{output}");
+}
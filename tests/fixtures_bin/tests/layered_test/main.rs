@@ -0,0 +1,13 @@
+//! Integration test harness with layers, in `tests/layered_test/src`
+
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    /// Test cases
+    #[depends_on(harness)]
+    extern crate cases;
+
+    /// Helpers for running the binary
+    extern crate harness;
+}
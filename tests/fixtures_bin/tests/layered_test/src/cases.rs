@@ -0,0 +1,16 @@
+crate::cases::use_deps!();
+
+#[test]
+fn run_binary() {
+    assert_eq!(harness::run_bin(&["out"]), "storing to out");
+}
+
+#[test]
+fn run_binary_default() {
+    assert_eq!(harness::run_bin(&[]), "storing to data");
+}
+
+#[test]
+fn run_example() {
+    assert_eq!(harness::run_example("layered_example"), "Hello, example!");
+}
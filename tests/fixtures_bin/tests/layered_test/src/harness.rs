@@ -0,0 +1,20 @@
+use std::process::Command;
+
+pub fn run_bin(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_fixtures_bin"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+pub fn run_example(name: &str) -> String {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(["run", "-q", "-p", "fixtures_bin", "--example", name])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
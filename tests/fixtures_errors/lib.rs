@@ -0,0 +1,15 @@
+//! Layers with errors, for the `errors` in the graph JSON.
+//! This crate doesn't compile on purpose
+
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(missing)]
+    pub mod api {}
+
+    pub mod utils {}
+
+    #[depends_on(utils)]
+    pub mod engine {}
+}
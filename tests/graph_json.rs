@@ -0,0 +1,77 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Build the fixtures with `LAYERED_CRATE_GRAPH_DIR` set, and compare
+/// the output with `tests/graph/*.json`.
+///
+/// Run with `GRAPH_JSON=overwrite` to write the expected files
+#[test]
+fn graph_json() {
+    let out_dir = check_fixture("tests/fixtures/Cargo.toml", "fixtures", true);
+    for name in [
+        "fixtures.src.json",
+        "fixtures.plugins.json",
+        "fixtures.engine.src.json",
+    ] {
+        compare(&out_dir, name);
+    }
+}
+
+/// Same as [`graph_json`], but for a crate that doesn't compile,
+/// so the JSON has `errors`
+#[test]
+fn graph_json_errors() {
+    let out_dir = check_fixture("tests/fixtures_errors/Cargo.toml", "fixtures_errors", false);
+    compare(&out_dir, "fixtures_errors.src.json");
+}
+
+/// Run `cargo check` on the fixture crate with the graph output enabled,
+/// and return the output directory
+fn check_fixture(manifest: &str, package: &str, should_compile: bool) -> std::path::PathBuf {
+    let target_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("target/graph-json");
+    let out_dir = target_dir.join("out").join(package);
+    let _ = std::fs::remove_dir_all(&out_dir);
+
+    // clean first, so the macro runs again
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(&cargo)
+        .args([
+            "clean",
+            "-p",
+            package,
+            "--manifest-path",
+            manifest,
+            "--target-dir",
+        ])
+        .arg(&target_dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let output = Command::new(&cargo)
+        .args(["check", "--manifest-path", manifest, "--target-dir"])
+        .arg(&target_dir)
+        .env("LAYERED_CRATE_GRAPH_DIR", &out_dir)
+        .output()
+        .unwrap();
+    assert_eq!(
+        output.status.success(),
+        should_compile,
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    out_dir
+}
+
+/// Compare the JSON file `name` in `out_dir` with the one in `tests/graph`
+fn compare(out_dir: &Path, name: &str) {
+    let actual = std::fs::read_to_string(out_dir.join(name)).unwrap();
+    let expected_path = Path::new("tests/graph").join(name);
+    if std::env::var("GRAPH_JSON").is_ok_and(|x| x == "overwrite") {
+        std::fs::write(&expected_path, actual).unwrap();
+        return;
+    }
+    let Ok(expected) = std::fs::read_to_string(&expected_path) else {
+        panic!("{name} is missing, run with GRAPH_JSON=overwrite to write it");
+    };
+    assert_eq!(actual, expected, "{name} is different");
+}
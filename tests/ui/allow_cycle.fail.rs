@@ -0,0 +1,59 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(y)]
+    #[allow_cycle(y, reason = "no cycle here")]
+    pub extern crate z;
+
+    #[depends_on(y)]
+    #[allow_cycle(y, reason = "moving the shared code from y to x")]
+    pub extern crate x;
+
+    #[depends_on(x)]
+    #[allow_cycle(x, reason = "moving the shared code from x to y")]
+    pub extern crate y;
+}
+
+#[layers]
+mod other {
+    #[depends_on(b)]
+    #[allow_cycle(b)]
+    #[allow_cycle(c, reason = "no such dependency")]
+    pub mod a {}
+
+    #[depends_on(b)]
+    #[allow_cycle(b, because = "the key is wrong")]
+    pub mod c {}
+
+    pub mod b {}
+}
+
+// only one of the edges has #[allow_cycle]
+#[layers]
+mod one_sided {
+    #[depends_on(second)]
+    pub mod first {}
+
+    #[depends_on(first)]
+    #[allow_cycle(first, reason = "second still uses first")]
+    pub mod second {}
+}
+
+// a new cycle through an allowed edge
+#[layers]
+mod through {
+    #[depends_on(engine)]
+    #[depends_on(utils)]
+    #[allow_cycle(engine, reason = "splitting engine from api")]
+    pub mod api {}
+
+    #[depends_on(engine)]
+    pub mod utils {}
+
+    #[depends_on(api)]
+    #[allow_cycle(api, reason = "splitting engine from api")]
+    pub mod engine {}
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use layered_crate::layers;
+
+#[layers(auto = "does_not_exist")]
+mod src {}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use layered_crate::layers;
+
+#[layers(max_depth = 3, max_fan_out = 2, max_fan_in = 2)]
+mod src {
+    #[max_depth(5)]
+    #[depends_on(a)]
+    #[depends_on(c)]
+    pub mod e {}
+
+    #[depends_on(b)]
+    #[depends_on(c)]
+    #[depends_on(d)]
+    pub mod a {}
+
+    #[depends_on(c)]
+    #[depends_on(d)]
+    pub mod b {}
+
+    #[max_fan_in(3)]
+    #[depends_on(d)]
+    pub mod c {}
+
+    pub mod d {}
+}
+
+fn main() {}
@@ -0,0 +1,387 @@
+#![allow(unused_imports)]
+
+use layered_crate::layers;
+
+// every module depends on every module below it, so the number of
+// dependency chains is exponential in the number of modules
+#[layers(max_depth = 25, doc_overview)]
+mod src {
+    #[depends_on(b)]
+    #[depends_on(c)]
+    #[depends_on(d)]
+    #[depends_on(e)]
+    #[depends_on(f)]
+    #[depends_on(g)]
+    #[depends_on(h)]
+    #[depends_on(i)]
+    #[depends_on(j)]
+    #[depends_on(k)]
+    #[depends_on(l)]
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod a {}
+
+    #[depends_on(c)]
+    #[depends_on(d)]
+    #[depends_on(e)]
+    #[depends_on(f)]
+    #[depends_on(g)]
+    #[depends_on(h)]
+    #[depends_on(i)]
+    #[depends_on(j)]
+    #[depends_on(k)]
+    #[depends_on(l)]
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod b {}
+
+    #[depends_on(d)]
+    #[depends_on(e)]
+    #[depends_on(f)]
+    #[depends_on(g)]
+    #[depends_on(h)]
+    #[depends_on(i)]
+    #[depends_on(j)]
+    #[depends_on(k)]
+    #[depends_on(l)]
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod c {}
+
+    #[depends_on(e)]
+    #[depends_on(f)]
+    #[depends_on(g)]
+    #[depends_on(h)]
+    #[depends_on(i)]
+    #[depends_on(j)]
+    #[depends_on(k)]
+    #[depends_on(l)]
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod d {}
+
+    #[depends_on(f)]
+    #[depends_on(g)]
+    #[depends_on(h)]
+    #[depends_on(i)]
+    #[depends_on(j)]
+    #[depends_on(k)]
+    #[depends_on(l)]
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod e {}
+
+    #[depends_on(g)]
+    #[depends_on(h)]
+    #[depends_on(i)]
+    #[depends_on(j)]
+    #[depends_on(k)]
+    #[depends_on(l)]
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod f {}
+
+    #[depends_on(h)]
+    #[depends_on(i)]
+    #[depends_on(j)]
+    #[depends_on(k)]
+    #[depends_on(l)]
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod g {}
+
+    #[depends_on(i)]
+    #[depends_on(j)]
+    #[depends_on(k)]
+    #[depends_on(l)]
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod h {}
+
+    #[depends_on(j)]
+    #[depends_on(k)]
+    #[depends_on(l)]
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod i {}
+
+    #[depends_on(k)]
+    #[depends_on(l)]
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod j {}
+
+    #[depends_on(l)]
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod k {}
+
+    #[depends_on(m)]
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod l {}
+
+    #[depends_on(n)]
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod m {}
+
+    #[depends_on(o)]
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod n {}
+
+    #[depends_on(p)]
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod o {}
+
+    #[depends_on(q)]
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod p {}
+
+    #[depends_on(r)]
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod q {}
+
+    #[depends_on(s)]
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod r {}
+
+    #[depends_on(t)]
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod s {}
+
+    #[depends_on(u)]
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod t {}
+
+    #[depends_on(v)]
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod u {}
+
+    #[depends_on(w)]
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod v {}
+
+    #[depends_on(x)]
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod w {}
+
+    #[depends_on(y)]
+    #[depends_on(z)]
+    pub mod x {}
+
+    #[depends_on(z)]
+    pub mod y {}
+
+    pub mod z {}
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use layered_crate::layers;
+
+#[layers(common = [utils, missing])]
+mod src {
+    #[depends_on(utils)]
+    pub mod api {}
+
+    #[depends_on(api)]
+    pub mod utils {}
+
+    #[common]
+    #[visible_to(api)]
+    pub mod errors {}
+}
+
+#[layers(common = [shared])]
+mod empty {
+    pub mod handler {}
+
+    #[visible_to()]
+    pub mod shared {}
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+layered_crate::define_layers! {
+    mod src {
+        pub x -> [y];
+        pub y -> [x];
+    }
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(storage)]
+    #[depends_on(legacy, deprecated = "inline what is still needed from `legacy`")]
+    pub mod api {
+        crate::api::use_deps!();
+
+        pub fn run() {
+            storage::save();
+            legacy::load();
+        }
+    }
+
+    #[deprecated(note = "use `storage_v2` instead")]
+    #[depends_on(legacy)]
+    pub mod storage {
+        crate::storage::use_deps!();
+
+        pub fn save() {
+            legacy::load();
+        }
+    }
+
+    pub mod legacy {
+        pub fn load() {}
+    }
+}
+
+fn main() {
+    api::run();
+    compile_error!("make it fail so we can check the warnings")
+}
@@ -0,0 +1,19 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(y)]
+    #[depends_on(z::a)]
+    #[depends_on(y)]
+    #[depends_on(z::b)]
+    pub mod x {}
+
+    pub mod y {}
+
+    pub mod z {
+        pub fn a() {}
+        pub fn b() {}
+    }
+}
+
+fn main() {}
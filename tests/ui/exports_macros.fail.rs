@@ -0,0 +1,36 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(logging)]
+    pub mod api {
+        crate::api::use_deps!();
+
+        pub fn run() {
+            log_event!("api");
+            logging::log_event!("api");
+        }
+    }
+
+    #[macro_use]
+    #[exports_macros(log_event, missing)]
+    pub mod logging {
+        macro_rules! log_event {
+            ($x:expr) => {
+                let _ = $x;
+            };
+        }
+        pub(crate) use log_event;
+    }
+
+    #[exports_macros()]
+    pub mod other {
+        pub fn leaked() {
+            log_event!("other");
+        }
+    }
+}
+
+fn main() {
+    api::run();
+}
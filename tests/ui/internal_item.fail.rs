@@ -0,0 +1,51 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(y)]
+    pub mod x {
+        use crate::x::crate_;
+
+        use crate_::y::helper;
+
+        pub fn do_something() {
+            helper();
+        }
+    }
+
+    pub mod y {
+        mod imp {
+            #[layered_crate::internal]
+            pub fn helper() {}
+
+            pub fn do_something() {
+                helper();
+            }
+        }
+        pub use imp::*;
+
+        mod other {
+            // visible to the whole layer
+            pub fn do_other() {
+                super::helper();
+                super::imp::helper();
+            }
+        }
+        pub use other::*;
+
+        pub fn do_more() {
+            helper();
+        }
+
+        #[layered_crate::internal]
+        fn not_pub() {}
+    }
+}
+
+fn main() {
+    x::do_something();
+    y::do_something();
+    y::do_other();
+    y::do_more();
+    y::helper();
+}
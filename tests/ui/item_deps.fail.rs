@@ -0,0 +1,33 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(utils::{parse, Config})]
+    pub mod api {
+        use crate::api::crate_;
+
+        use crate_::utils::{format, parse};
+
+        pub fn run() {
+            parse();
+            format();
+        }
+    }
+
+    pub mod utils {
+        pub fn parse() {}
+        pub fn format() {}
+    }
+}
+
+#[layers]
+mod empty {
+    #[depends_on(b::{})]
+    pub mod a {}
+
+    pub mod b {}
+}
+
+fn main() {
+    api::run();
+}
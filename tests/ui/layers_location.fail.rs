@@ -0,0 +1,39 @@
+mod nested {
+    use layered_crate::layers;
+
+    #[layers]
+    mod src {
+        #[depends_on(b)]
+        pub mod a {
+            crate::nested::a::use_deps!();
+        }
+        pub mod b {}
+    }
+}
+
+mod other {
+    use layered_crate::layers;
+
+    #[layers(at = crate::not_here)]
+    mod src {
+        #[depends_on(b)]
+        pub mod a {
+            crate::other::a::use_deps!();
+        }
+        pub mod b {}
+    }
+}
+
+// no `use_deps!()`, so `at` is not needed
+mod without_at {
+    use layered_crate::layers;
+
+    #[layers]
+    mod src {
+        #[depends_on(b)]
+        pub mod a {}
+        pub mod b {}
+    }
+}
+
+fn main() {}
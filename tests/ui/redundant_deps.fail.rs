@@ -0,0 +1,22 @@
+use layered_crate::layers;
+
+#[layers(check_redundant_deps)]
+mod src {
+    #[depends_on(sub_system_1)]
+    #[depends_on(sub_system_2)]
+    #[depends_on(utils)]
+    pub mod api {}
+
+    #[depends_on(sub_system_2)]
+    pub mod sub_system_1 {}
+
+    #[depends_on(utils)]
+    pub mod sub_system_2 {}
+
+    pub mod utils {}
+}
+
+#[layers(unknown)]
+mod src2 {}
+
+fn main() {}
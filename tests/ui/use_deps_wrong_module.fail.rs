@@ -0,0 +1,26 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(y)]
+    pub mod x {
+        crate::x::use_deps!();
+
+        pub fn do_something() {
+            y::do_something();
+        }
+    }
+
+    #[depends_on(z)]
+    pub mod y {
+        crate::x::use_deps!();
+
+        pub fn do_something() {}
+    }
+
+    pub mod z {}
+}
+
+fn main() {
+    x::do_something();
+}
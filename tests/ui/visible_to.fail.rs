@@ -0,0 +1,23 @@
+use layered_crate::layers;
+
+#[layers]
+mod src {
+    #[depends_on(unsafe_ffi)]
+    pub mod api {}
+
+    #[depends_on(unsafe_ffi)]
+    pub mod platform {}
+
+    #[visible_to(platform, missing)]
+    pub mod unsafe_ffi {}
+}
+
+#[layers]
+mod empty {
+    pub mod handler {}
+
+    #[visible_to()]
+    pub mod hidden {}
+}
+
+fn main() {}